@@ -1,17 +1,25 @@
+use std::collections::BTreeSet;
 use std::fmt::{self, Display, Formatter};
 use std::ops::Deref;
-use std::slice::Iter;
+use std::slice::{Iter, IterMut};
 
-use equations::Align;
+use equations::{Align, Equation, SplitEquation};
+use failure::Error;
+use figure::Figure;
+use listing::{Listing, MintedListing};
 use lists::List;
-use paragraph::Paragraph;
+use paragraph::{Paragraph, ParagraphElement};
 use section::Section;
+use theorem::Theorem;
+use visitor::{print, Visitor, VisitorMut};
 
 /// The root Document node.
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Document {
     /// The document class.
     pub class: DocumentClass,
+    /// Any options passed to the document class (e.g. `12pt` or `a4paper`).
+    pub class_options: Vec<String>,
     /// The `Document`'s preamble.
     pub preamble: Preamble,
     /// The various elements inside this `Document`.
@@ -27,6 +35,12 @@ impl Document {
         }
     }
 
+    /// Add an option to the document class.
+    pub fn add_class_option(&mut self, opt: &str) -> &mut Self {
+        self.class_options.push(opt.to_string());
+        self
+    }
+
     /// Add an element to the `Document`.
     ///
     /// To make this work as seamlessly as possible, it will accept anything
@@ -40,11 +54,110 @@ impl Document {
         self
     }
 
+    /// Insert an element at the specified position, shifting everything after
+    /// it along by one.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `index` is greater than the number of elements in
+    /// the `Document`.
+    pub fn insert<E>(&mut self, index: usize, element: E) -> &mut Self
+    where
+        E: Into<Element>,
+    {
+        self.elements.insert(index, element.into());
+        self
+    }
+
+    /// Remove and return the element at the specified position.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Element {
+        self.elements.remove(index)
+    }
+
     /// Iterate over the Elements in this document.
-    pub fn iter(&self) -> Iter<Element> {
+    pub fn iter(&self) -> Iter<'_, Element> {
         self.elements.iter()
     }
 
+    /// Mutably iterate over the Elements in this document.
+    pub fn iter_mut(&mut self) -> IterMut<'_, Element> {
+        self.elements.iter_mut()
+    }
+
+    /// The number of top-level elements in this document.
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Does this document contain any elements?
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Replace every occurrence of `from` with `to` in the document's plain
+    /// paragraph text and `UserDefined` elements, returning the number of
+    /// replacements made.
+    ///
+    /// This is handy for filling in placeholders when using a document as a
+    /// template.
+    ///
+    /// ```rust
+    /// use latex::{Document, DocumentClass};
+    ///
+    /// let mut doc = Document::new(DocumentClass::Article);
+    /// doc.push("Dear {{name}},");
+    ///
+    /// assert_eq!(doc.replace_text("{{name}}", "Michael"), 1);
+    /// ```
+    pub fn replace_text(&mut self, from: &str, to: &str) -> usize {
+        if from.is_empty() {
+            return 0;
+        }
+
+        let mut replacer = ReplaceText {
+            from,
+            to,
+            replacements: 0,
+        };
+        replacer
+            .visit_document(self)
+            .expect("Replacing text never fails");
+
+        replacer.replacements
+    }
+
+    /// Get every label defined in the document (on sections, equations,
+    /// figures, and listings), in the order they appear.
+    ///
+    /// Any duplicates are kept, making this useful for checking
+    /// cross-references before compiling.
+    pub fn labels(&self) -> Vec<String> {
+        let mut collector = CollectLabels::default();
+        collector
+            .visit_document(self)
+            .expect("Collecting labels never fails");
+
+        collector.labels
+    }
+
+    /// Get the packages needed by the elements used in this document and its
+    /// preamble (e.g. `amsmath` for an `Align`), sorted alphabetically.
+    ///
+    /// This doesn't check whether the packages have already been added to
+    /// the preamble.
+    pub fn required_packages(&self) -> Vec<String> {
+        let mut collector = RequiredPackages::default();
+        collector
+            .visit_document(self)
+            .expect("Collecting packages never fails");
+
+        collector.packages.into_iter().map(String::from).collect()
+    }
+
     /// A convience method to include one document into
     /// another by cloning the individual nodes.
     pub fn push_doc(&mut self, doc: &Document) -> &mut Self {
@@ -53,6 +166,35 @@ impl Document {
         }
         self
     }
+
+    /// Merge another document's preamble into this one.
+    ///
+    /// Package imports, commands, and any other preamble elements are copied
+    /// across unless an identical one is already present. The title, author,
+    /// and date are only copied if they haven't been set on this document,
+    /// and the merged preamble is sorted on render if either one was.
+    pub fn merge_preamble(&mut self, other: &Document) -> &mut Self {
+        let preamble = &mut self.preamble;
+
+        for element in other.preamble.iter() {
+            if !preamble.iter().any(|existing| existing == element) {
+                preamble.push(element.clone());
+            }
+        }
+
+        if preamble.title.is_none() {
+            preamble.title = other.preamble.title.clone();
+        }
+        if preamble.author.is_none() {
+            preamble.author = other.preamble.author.clone();
+        }
+        if preamble.date.is_none() {
+            preamble.date = other.preamble.date.clone();
+        }
+        preamble.sort_on_render |= other.preamble.sort_on_render;
+
+        self
+    }
 }
 
 impl Deref for Document {
@@ -64,6 +206,201 @@ impl Deref for Document {
     }
 }
 
+/// The `VisitorMut` used by `Document::replace_text()`.
+struct ReplaceText<'a> {
+    from: &'a str,
+    to: &'a str,
+    replacements: usize,
+}
+
+impl<'a> ReplaceText<'a> {
+    fn replace(&mut self, text: &mut String) {
+        let occurrences = text.matches(self.from).count();
+
+        if occurrences > 0 {
+            *text = text.replace(self.from, self.to);
+            self.replacements += occurrences;
+        }
+    }
+}
+
+impl<'a> VisitorMut for ReplaceText<'a> {
+    fn visit_paragraph_element(&mut self, element: &mut ParagraphElement) -> Result<(), Error> {
+        if let ParagraphElement::Plain(ref mut s) = *element {
+            self.replace(s);
+        }
+
+        if let Some(child) = element.children_mut() {
+            self.visit_paragraph_element(child)?;
+        }
+
+        Ok(())
+    }
+
+    fn visit_user_defined_line(&mut self, line: &mut String) -> Result<(), Error> {
+        self.replace(line);
+        Ok(())
+    }
+}
+
+/// The `Visitor` used by `Document::labels()`.
+#[derive(Default)]
+struct CollectLabels {
+    labels: Vec<String>,
+}
+
+impl Visitor for CollectLabels {
+    fn visit_section(&mut self, section: &Section) -> Result<(), Error> {
+        if let Some(label) = section.get_label() {
+            self.labels.push(label.to_string());
+        }
+
+        for element in section.iter() {
+            self.visit_element(element)?;
+        }
+
+        Ok(())
+    }
+
+    fn visit_equation(&mut self, equation: &Equation) -> Result<(), Error> {
+        if let Some(label) = equation.get_label() {
+            self.labels.push(label.to_string());
+        }
+
+        Ok(())
+    }
+
+    fn visit_split_equation(&mut self, equation: &SplitEquation) -> Result<(), Error> {
+        self.labels.extend(equation.label.clone());
+        Ok(())
+    }
+
+    fn visit_figure(&mut self, figure: &Figure) -> Result<(), Error> {
+        self.labels.extend(figure.label.clone());
+        Ok(())
+    }
+
+    fn visit_listing(&mut self, listing: &Listing) -> Result<(), Error> {
+        self.labels.extend(listing.label.clone());
+        Ok(())
+    }
+}
+
+/// The `Visitor` used by `Document::required_packages()`.
+#[derive(Default)]
+struct RequiredPackages {
+    packages: BTreeSet<&'static str>,
+}
+
+impl Visitor for RequiredPackages {
+    fn visit_align(&mut self, _: &Align) -> Result<(), Error> {
+        self.packages.insert("amsmath");
+        Ok(())
+    }
+
+    fn visit_split_equation(&mut self, _: &SplitEquation) -> Result<(), Error> {
+        self.packages.insert("amsmath");
+        Ok(())
+    }
+
+    fn visit_figure(&mut self, figure: &Figure) -> Result<(), Error> {
+        self.packages.insert("graphicx");
+        if figure.placement.unwrap_or_default().exactly_here {
+            self.packages.insert("float");
+        }
+
+        Ok(())
+    }
+
+    fn visit_multicols(&mut self, _: usize, body: &[Element]) -> Result<(), Error> {
+        self.packages.insert("multicol");
+        for element in body {
+            self.visit_element(element)?;
+        }
+
+        Ok(())
+    }
+
+    fn visit_tikz_picture(&mut self, _: Option<&str>, _: &str) -> Result<(), Error> {
+        self.packages.insert("tikz");
+        Ok(())
+    }
+
+    fn visit_print_bibliography(&mut self) -> Result<(), Error> {
+        self.packages.insert("biblatex");
+        Ok(())
+    }
+
+    fn visit_preamble(&mut self, preamble: &Preamble) -> Result<(), Error> {
+        for element in preamble.iter() {
+            match *element {
+                PreambleElement::AddBibResource(_) => {
+                    self.packages.insert("biblatex");
+                }
+                PreambleElement::HyperSetup(_) => {
+                    self.packages.insert("hyperref");
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn visit_listing(&mut self, _: &Listing) -> Result<(), Error> {
+        self.packages.insert("listings");
+        Ok(())
+    }
+
+    fn visit_minted_listing(&mut self, _: &MintedListing) -> Result<(), Error> {
+        self.packages.insert("minted");
+        Ok(())
+    }
+
+    fn visit_paragraph_element(&mut self, element: &ParagraphElement) -> Result<(), Error> {
+        match *element {
+            ParagraphElement::Strikeout(_) => {
+                self.packages.insert("ulem");
+            }
+            ParagraphElement::Link { .. } | ParagraphElement::Url(_) => {
+                self.packages.insert("hyperref");
+            }
+            ParagraphElement::Colored { .. } => {
+                self.packages.insert("xcolor");
+            }
+            _ => {}
+        }
+
+        if let Some(child) = element.children() {
+            self.visit_paragraph_element(child)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> IntoIterator for &'a Document {
+    type Item = &'a Element;
+    type IntoIter = Iter<'a, Element>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Display for Document {
+    /// Render the `Document` using `latex::print()`.
+    ///
+    /// Because `fmt::Error` can't carry any information, if printing fails
+    /// (e.g. a `\verb` has no usable delimiter) the underlying error is
+    /// discarded. Use `latex::print()` directly if you need to know what went
+    /// wrong.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let rendered = print(self).map_err(|_| fmt::Error)?;
+        f.write_str(&rendered)
+    }
+}
+
 /// The major elements in a `Document`, representing each type of possible
 /// node.
 ///
@@ -84,15 +421,45 @@ pub enum Element {
     Section(Section),
     /// The table of contents.
     TableOfContents,
+    /// A list of every figure in the document.
+    ListOfFigures,
+    /// A list of every table in the document.
+    ListOfTables,
     /// The title page.
     TitlePage,
     /// Clear the page.
     ClearPage,
+    /// Start the appendices, so any following sections are lettered (`A`,
+    /// `B`, ...) instead of numbered.
+    Appendix,
+    /// Start a book's front matter (preface, table of contents, etc.), where
+    /// pages are numbered with roman numerals and chapters aren't numbered.
+    FrontMatter,
+    /// Start a book's main matter, switching back to arabic page numbers and
+    /// numbered chapters.
+    MainMatter,
+    /// Start a book's back matter (bibliography, index, etc.), where chapters
+    /// aren't numbered.
+    BackMatter,
     /// An `align` environment for containing a bunch of equations.
     Align(Align),
+    /// A single equation split over multiple lines.
+    SplitEquation(SplitEquation),
 
     /// A generic environment and its lines.
     Environment(String, Vec<String>),
+    /// A generic environment which takes optional and/or mandatory
+    /// arguments, rendered as `\begin{name}[options]{arg1}{arg2}`.
+    EnvironmentWith {
+        /// The environment's name.
+        name: String,
+        /// Optional arguments, joined with commas inside square brackets.
+        options: Vec<String>,
+        /// Mandatory arguments, each wrapped in curly braces.
+        args: Vec<String>,
+        /// The environment's lines.
+        lines: Vec<String>,
+    },
 
     /// Any other element.
     ///
@@ -105,6 +472,88 @@ pub enum Element {
     List(List),
     /// A generic include statement
     Input(String),
+    /// The document's abstract, made up of one or more paragraphs.
+    Abstract(Vec<Paragraph>),
+    /// A figure containing an image.
+    Figure(Figure),
+    /// A theorem-like environment.
+    Theorem(Theorem),
+    /// A BibTeX bibliography, rendered using `\bibliographystyle` and
+    /// `\bibliography`.
+    Bibliography {
+        /// The bibliography style (e.g. `plain`).
+        style: String,
+        /// The `.bib` file(s) containing the bibliography entries.
+        sources: String,
+    },
+    /// Print a `biblatex` bibliography.
+    PrintBibliography,
+    /// A block of source code.
+    Listing(Listing),
+    /// A block of source code highlighted using `minted`.
+    MintedListing(MintedListing),
+    /// A `center` environment containing other elements.
+    Center(Vec<Element>),
+    /// Lay out the body over several columns using the `multicols`
+    /// environment.
+    ///
+    /// # Note
+    ///
+    /// This requires you to include the `multicol` package in your preamble.
+    MultiCols {
+        /// The number of columns.
+        columns: usize,
+        /// The elements laid out in columns.
+        body: Vec<Element>,
+    },
+    /// Set a counter's value using `\setcounter`.
+    SetCounter {
+        /// The counter's name.
+        name: String,
+        /// The counter's new value.
+        value: i64,
+    },
+    /// A TeX comment. Each line is rendered with a leading `% `, so
+    /// multi-line comments are fine.
+    Comment(String),
+    /// A `minipage` environment, useful for placing content side-by-side.
+    Minipage {
+        /// The width of the minipage (e.g. `0.5\textwidth`).
+        width: String,
+        /// The elements inside the minipage.
+        body: Vec<Element>,
+    },
+    /// A diagram drawn using a `tikzpicture` environment.
+    ///
+    /// # Note
+    ///
+    /// This requires you to include the `tikz` package in your preamble.
+    TikzPicture {
+        /// Options passed to the environment (e.g. `scale=2`).
+        options: Option<String>,
+        /// The raw TeX used to draw the picture.
+        body: String,
+    },
+    /// An arbitrary environment containing other elements, unlike
+    /// `Environment` which only contains raw lines.
+    Block {
+        /// The environment's name.
+        name: String,
+        /// The elements inside the environment.
+        body: Vec<Element>,
+    },
+    /// Vertical space between elements, rendered using `\vspace` (e.g. `1cm`
+    /// or `\fill`).
+    VSpace(String),
+    /// A horizontal line spanning the width of the text.
+    HorizontalRule,
+    /// A horizontal line with a custom width and thickness.
+    Rule {
+        /// How long the line is (e.g. `0.5\textwidth`).
+        width: String,
+        /// How thick the line is (e.g. `1pt`).
+        thickness: String,
+    },
 
     // Add a dummy element so we can expand later on without breaking stuff
     #[doc(hidden)]
@@ -136,6 +585,36 @@ impl From<Align> for Element {
     }
 }
 
+impl From<SplitEquation> for Element {
+    fn from(other: SplitEquation) -> Self {
+        Element::SplitEquation(other)
+    }
+}
+
+impl From<Figure> for Element {
+    fn from(other: Figure) -> Self {
+        Element::Figure(other)
+    }
+}
+
+impl From<Theorem> for Element {
+    fn from(other: Theorem) -> Self {
+        Element::Theorem(other)
+    }
+}
+
+impl From<Listing> for Element {
+    fn from(other: Listing) -> Self {
+        Element::Listing(other)
+    }
+}
+
+impl From<MintedListing> for Element {
+    fn from(other: MintedListing) -> Self {
+        Element::MintedListing(other)
+    }
+}
+
 impl From<Section> for Element {
     fn from(other: Section) -> Self {
         Element::Section(other)
@@ -172,6 +651,7 @@ pub enum DocumentClass {
     Other(String),
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for DocumentClass {
     fn default() -> Self {
         DocumentClass::Article
@@ -191,11 +671,11 @@ impl Display for DocumentClass {
 }
 
 impl Extend<Element> for Document {
-    fn extend<T: IntoIterator<Item=Element>>(&mut self, iter:T) {
-    for elem in iter {
-      self.push(elem);
+    fn extend<T: IntoIterator<Item = Element>>(&mut self, iter: T) {
+        for elem in iter {
+            self.push(elem);
+        }
     }
-  }
 }
 
 /// An element of the document's preamble.
@@ -212,7 +692,54 @@ pub enum PreambleElement {
         name: String,
         args_num: Option<usize>,
         default_arg: Option<String>,
-        definition: String
+        definition: String,
+    },
+    /// Create a `\renewcommand` line in latex, redefining an existing command
+    RenewCommand {
+        name: String,
+        args_num: Option<usize>,
+        default_arg: Option<String>,
+        definition: String,
+    },
+    /// Create a `\newenvironment` line in latex
+    NewEnvironment {
+        name: String,
+        args_num: Option<usize>,
+        begin_def: String,
+        end_def: String,
+    },
+    /// Declare a theorem-like environment with `\newtheorem`, optionally
+    /// numbered within another counter (e.g. `section`)
+    NewTheorem {
+        env: String,
+        display: String,
+        counter: Option<String>,
+    },
+    /// Add a `.bib` file to be used by `biblatex` with `\addbibresource`
+    AddBibResource(String),
+    /// Configure the `hyperref` package using `\hypersetup{key=value,...}`.
+    ///
+    /// Each value is wrapped in braces when it is rendered, so values may
+    /// contain commas.
+    HyperSetup(Vec<(String, String)>),
+    /// The directories `\includegraphics` should search for images, rendered
+    /// using `\graphicspath`.
+    GraphicsPath(Vec<String>),
+    /// Only compile the listed files when they are `\include`d, using
+    /// `\includeonly`.
+    IncludeOnly(Vec<String>),
+    /// Declare a new counter using `\newcounter`, optionally reset whenever
+    /// the `within` counter is stepped (e.g. `section`).
+    NewCounter {
+        name: String,
+        within: Option<String>,
+    },
+    /// Set a length register (e.g. `parindent`) using `\setlength`.
+    SetLength {
+        /// The register's name, without the leading backslash.
+        register: String,
+        /// The new value (e.g. `0pt`).
+        value: String,
     },
     /// An escape hatch for including an arbitrary bit of TeX in a preamble.
     UserDefined(String),
@@ -225,54 +752,327 @@ pub struct Preamble {
     pub author: Option<String>,
     /// An optional title for the document.
     pub title: Option<String>,
+    /// The date shown on the title page. LaTeX uses today's date if this
+    /// isn't set.
+    pub date: Option<String>,
+    sort_on_render: bool,
     contents: Vec<PreambleElement>,
 }
 
 impl Preamble {
-    /// Set the document's author.
+    /// Set the document's author, replacing any existing authors.
     pub fn author(&mut self, name: &str) -> &mut Self {
         self.author = Some(name.to_string());
         self
     }
 
+    /// Add another author to the document, separating them from any
+    /// existing authors with `\and`.
+    pub fn add_author(&mut self, name: &str) -> &mut Self {
+        self.author = Some(match self.author.take() {
+            Some(existing) => format!(r"{} \and {}", existing, name),
+            None => name.to_string(),
+        });
+        self
+    }
+
     /// Set the document title.
     pub fn title(&mut self, name: &str) -> &mut Self {
         self.title = Some(name.to_string());
         self
     }
 
+    /// Set the date shown on the title page.
+    ///
+    /// Passing in an empty string will suppress the date altogether.
+    pub fn date(&mut self, d: &str) -> &mut Self {
+        self.date = Some(d.to_string());
+        self
+    }
+
+    /// Set the date shown on the title page to the day the document is
+    /// compiled (`\today`).
+    pub fn today(&mut self) -> &mut Self {
+        self.date(r"\today")
+    }
+
     /// Add a package import to the preamble.
+    ///
+    /// Importing a package which has already been imported (with the same
+    /// options) does nothing.
     pub fn use_package(&mut self, name: &str) -> &mut Self {
-        self.contents.push(PreambleElement::UsePackage {
+        self.add_package(PreambleElement::UsePackage {
             package: name.to_string(),
             argument: None,
-        });
+        })
+    }
+
+    /// Add a package import with some options to the preamble (e.g.
+    /// `\usepackage[margin=1in]{geometry}`).
+    ///
+    /// Importing a package which has already been imported with the same
+    /// options does nothing.
+    pub fn use_package_with(&mut self, name: &str, options: &str) -> &mut Self {
+        self.add_package(PreambleElement::UsePackage {
+            package: name.to_string(),
+            argument: Some(options.to_string()),
+        })
+    }
+
+    /// Push a `UsePackage` element, unless an identical one already exists.
+    fn add_package(&mut self, import: PreambleElement) -> &mut Self {
+        if !self.contents.contains(&import) {
+            self.contents.push(import);
+        }
         self
     }
 
     /// Interface of most commonly used way to write a `/newcommand` line in latex.  
-    /// If you want to create `/newcommand` in 
-    /// other ways(like add default argument or do not assign the num of arguments), 
-    /// please use `push` method in `Preamble` struct. 
-    pub fn new_command(
+    /// If you want to create `/newcommand` in
+    /// other ways(like add default argument or do not assign the num of arguments),
+    /// please use `push` method in `Preamble` struct.
+    pub fn new_command(&mut self, name: &str, args_num: usize, definition: &str) -> &mut Self {
+        self.contents.push(PreambleElement::NewCommand {
+            name: String::from(name),
+            args_num: Some(args_num),
+            default_arg: None,
+            definition: String::from(definition),
+        });
+        self
+    }
+
+    /// Redefine an existing command (e.g. `\abstractname`) which takes no
+    /// arguments.
+    /// If the command takes arguments, please use `push` method in `Preamble`
+    /// struct with a `PreambleElement::RenewCommand`.
+    pub fn renew_command(&mut self, name: &str, definition: &str) -> &mut Self {
+        self.contents.push(PreambleElement::RenewCommand {
+            name: name.to_string(),
+            args_num: None,
+            default_arg: None,
+            definition: definition.to_string(),
+        });
+        self
+    }
+
+    /// Define a new environment which can then be used with
+    /// `Element::Environment`.
+    ///
+    /// Environments which take no arguments (`args_num == 0`) are declared
+    /// without the `[n]`.
+    pub fn new_environment(
         &mut self,
         name: &str,
         args_num: usize,
-        definition: &str
+        begin_def: &str,
+        end_def: &str,
     ) -> &mut Self {
-        self.contents.push(
-            PreambleElement::NewCommand {
-                name: String::from(name),
-                args_num: Some(args_num),
-                default_arg: None,
-                definition: String::from(definition)
-            }
-        );
+        self.contents.push(PreambleElement::NewEnvironment {
+            name: name.to_string(),
+            args_num: if args_num == 0 { None } else { Some(args_num) },
+            begin_def: begin_def.to_string(),
+            end_def: end_def.to_string(),
+        });
+        self
+    }
+
+    /// Declare a theorem-like environment (e.g. `theorem` displayed as
+    /// "Theorem") so it can be used with a `Theorem` element.
+    pub fn new_theorem(&mut self, env: &str, display: &str) -> &mut Self {
+        self.contents.push(PreambleElement::NewTheorem {
+            env: env.to_string(),
+            display: display.to_string(),
+            counter: None,
+        });
+        self
+    }
+
+    /// Set the page margins and paper size using the `geometry` package.
+    ///
+    /// Calling this again replaces the previous settings, because importing
+    /// `geometry` twice with different options is an error.
+    pub fn geometry(&mut self, g: PageGeometry) -> &mut Self {
+        let import = PreambleElement::UsePackage {
+            package: "geometry".to_string(),
+            argument: Some(g.options().join(",")),
+        };
+
+        let existing = self.contents.iter().position(|element| match *element {
+            PreambleElement::UsePackage { ref package, .. } => package == "geometry",
+            _ => false,
+        });
+        match existing {
+            Some(index) => self.contents[index] = import,
+            None => self.contents.push(import),
+        }
+
+        self
+    }
+
+    /// Use custom page headers and footers, importing the `fancyhdr` package.
+    pub fn fancy_header(&mut self, header: &FancyHeader) -> &mut Self {
+        self.use_package("fancyhdr");
+        for line in header.lines() {
+            self.contents.push(PreambleElement::UserDefined(line));
+        }
+        self
+    }
+
+    /// Set the spacing between lines (e.g. `2.0` for double spacing) using
+    /// the `setspace` package.
+    ///
+    /// One-and-a-half and double spacing use `\onehalfspacing` and
+    /// `\doublespacing`, anything else uses `\setstretch`. Calling this again
+    /// replaces the previous spacing.
+    pub fn line_spacing(&mut self, factor: f64) -> &mut Self {
+        // both values are exactly representable, so comparing is fine
+        let command = if factor == 1.5 {
+            r"\onehalfspacing".to_string()
+        } else if factor == 2.0 {
+            r"\doublespacing".to_string()
+        } else {
+            format!(r"\setstretch{{{}}}", factor)
+        };
+
+        self.use_package("setspace");
+
+        let existing = self.contents.iter().position(|element| match *element {
+            PreambleElement::UserDefined(ref line) => is_line_spacing(line),
+            _ => false,
+        });
+        match existing {
+            Some(index) => self.contents[index] = PreambleElement::UserDefined(command),
+            None => self.contents.push(PreambleElement::UserDefined(command)),
+        }
+
+        self
+    }
+
+    /// Configure the `hyperref` package (e.g. `colorlinks=true`).
+    ///
+    /// Values are wrapped in braces when rendered, so they shouldn't include
+    /// their own.
+    ///
+    /// # Note
+    ///
+    /// This requires you to include the `hyperref` package in your preamble.
+    pub fn hypersetup(&mut self, options: &[(&str, &str)]) -> &mut Self {
+        let options = options
+            .iter()
+            .map(|&(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        self.contents.push(PreambleElement::HyperSetup(options));
+        self
+    }
+
+    /// Set the title stored in the PDF's metadata.
+    ///
+    /// The `pdf_*()` methods all add their options to a single
+    /// `\hypersetup`, and require the `hyperref` package.
+    pub fn pdf_title(&mut self, title: &str) -> &mut Self {
+        self.pdf_metadata("pdftitle", title)
+    }
+
+    /// Set the author stored in the PDF's metadata.
+    pub fn pdf_author(&mut self, author: &str) -> &mut Self {
+        self.pdf_metadata("pdfauthor", author)
+    }
+
+    /// Set the subject stored in the PDF's metadata.
+    pub fn pdf_subject(&mut self, subject: &str) -> &mut Self {
+        self.pdf_metadata("pdfsubject", subject)
+    }
+
+    /// Set the keywords stored in the PDF's metadata.
+    pub fn pdf_keywords(&mut self, keywords: &[&str]) -> &mut Self {
+        self.pdf_metadata("pdfkeywords", &keywords.join(", "))
+    }
+
+    /// Set a `\hypersetup` option, adding it to the existing `HyperSetup`
+    /// element if there is one.
+    fn pdf_metadata(&mut self, key: &str, value: &str) -> &mut Self {
+        let value = value.to_string();
+
+        let existing = self.contents.iter_mut().rev().find_map(|item| match *item {
+            PreambleElement::HyperSetup(ref mut options) => Some(options),
+            _ => None,
+        });
+
+        match existing {
+            Some(options) => match options.iter_mut().find(|option| option.0 == key) {
+                Some(option) => option.1 = value,
+                None => options.push((key.to_string(), value)),
+            },
+            None => self
+                .contents
+                .push(PreambleElement::HyperSetup(vec![(key.to_string(), value)])),
+        }
+
+        self
+    }
+
+    /// Add directories for `\includegraphics` to search when looking for
+    /// images. A trailing `/` is added to each path if it is missing.
+    ///
+    /// # Note
+    ///
+    /// This requires you to include the `graphicx` package in your preamble.
+    pub fn graphics_path(&mut self, paths: &[&str]) -> &mut Self {
+        let paths = paths
+            .iter()
+            .map(|path| {
+                if path.ends_with('/') {
+                    path.to_string()
+                } else {
+                    format!("{}/", path)
+                }
+            })
+            .collect();
+        self.contents.push(PreambleElement::GraphicsPath(paths));
+        self
+    }
+
+    /// Only compile the specified files when they are `\include`d, letting
+    /// you work on part of a large document at a time.
+    pub fn include_only(&mut self, files: &[&str]) -> &mut Self {
+        let files = files.iter().map(|f| f.to_string()).collect();
+        self.contents.push(PreambleElement::IncludeOnly(files));
+        self
+    }
+
+    /// Declare a new counter.
+    ///
+    /// If the counter should be reset by another counter, please use the
+    /// `push` method instead.
+    pub fn new_counter(&mut self, name: &str) -> &mut Self {
+        self.contents.push(PreambleElement::NewCounter {
+            name: name.to_string(),
+            within: None,
+        });
+        self
+    }
+
+    /// Set a length register, for example `set_length("parindent", "0pt")`
+    /// to stop paragraphs from being indented.
+    ///
+    /// The leading backslash on the register's name is optional.
+    pub fn set_length(&mut self, register: &str, value: &str) -> &mut Self {
+        self.contents.push(PreambleElement::SetLength {
+            register: register.trim_start_matches('\\').to_string(),
+            value: value.to_string(),
+        });
+        self
+    }
+
+    /// Add a `.bib` file for `biblatex` to load bibliography entries from.
+    pub fn add_bib_resource(&mut self, path: &str) -> &mut Self {
+        self.contents
+            .push(PreambleElement::AddBibResource(path.to_string()));
         self
     }
 
     /// Iterate over each package used in the Preamble.
-    pub fn iter(&self) -> Iter<PreambleElement> {
+    pub fn iter(&self) -> Iter<'_, PreambleElement> {
         self.contents.iter()
     }
 
@@ -281,6 +1081,23 @@ impl Preamble {
         self.contents.is_empty()
     }
 
+    /// Set whether the preamble should be reordered when it is rendered, so
+    /// all package imports come first, followed by command, environment, and
+    /// theorem definitions, then any `UserDefined` lines.
+    ///
+    /// Elements of the same kind keep their original order. This is disabled
+    /// by default, meaning elements are rendered in the order they were
+    /// added.
+    pub fn sort_on_render(&mut self, yes: bool) -> &mut Self {
+        self.sort_on_render = yes;
+        self
+    }
+
+    /// Will the preamble be reordered when it is rendered?
+    pub fn sorts_on_render(&self) -> bool {
+        self.sort_on_render
+    }
+
     /// Add a PreambleElement to the `Preamble`.
     ///
     /// To make this work as seamlessly as possible, it will accept anything
@@ -293,13 +1110,425 @@ impl Preamble {
         self.contents.push(element.into());
         self
     }
-
 }
 
 impl Extend<PreambleElement> for Preamble {
-    fn extend<T: IntoIterator<Item=PreambleElement>>(&mut self, iter:T) {
-    for elem in iter {
-      self.push(elem);
+    fn extend<T: IntoIterator<Item = PreambleElement>>(&mut self, iter: T) {
+        for elem in iter {
+            self.push(elem);
+        }
+    }
+}
+
+/// The page layout passed to the `geometry` package.
+///
+/// Any fields which aren't set will be left out, leaving them at
+/// `geometry`'s defaults.
+///
+/// # Examples
+///
+/// ```rust
+/// use latex::{Document, DocumentClass, PageGeometry};
+///
+/// let mut doc = Document::new(DocumentClass::Article);
+/// doc.preamble.geometry(PageGeometry {
+///     margin: Some("1in".to_string()),
+///     paper: Some("a4paper".to_string()),
+///     ..Default::default()
+/// });
+/// ```
+///
+/// This will add the following line to the preamble:
+///
+/// ```tex
+/// \usepackage[paper=a4paper,margin=1in]{geometry}
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PageGeometry {
+    /// The paper size (e.g. `a4paper`).
+    pub paper: Option<String>,
+    /// The margin used on all four sides of the page.
+    pub margin: Option<String>,
+    /// The top margin.
+    pub top: Option<String>,
+    /// The bottom margin.
+    pub bottom: Option<String>,
+    /// The left margin.
+    pub left: Option<String>,
+    /// The right margin.
+    pub right: Option<String>,
+}
+
+impl PageGeometry {
+    /// Get the options passed to the `geometry` package (e.g. `margin=1in`).
+    pub fn options(&self) -> Vec<String> {
+        let fields = [
+            ("paper", &self.paper),
+            ("margin", &self.margin),
+            ("top", &self.top),
+            ("bottom", &self.bottom),
+            ("left", &self.left),
+            ("right", &self.right),
+        ];
+
+        fields
+            .iter()
+            .filter_map(|&(key, value)| value.as_ref().map(|v| format!("{}={}", key, v)))
+            .collect()
+    }
+}
+
+/// Is this one of the lines written by `Preamble::line_spacing()`?
+fn is_line_spacing(line: &str) -> bool {
+    line == r"\onehalfspacing" || line == r"\doublespacing" || line.starts_with(r"\setstretch{")
+}
+
+/// Page headers and footers, set using the `fancyhdr` package.
+///
+/// The default headers and footers are cleared (using `\fancyhf{}`), so only
+/// the parts which have been set will be shown.
+///
+/// # Examples
+///
+/// ```rust
+/// use latex::{Document, DocumentClass, FancyHeader};
+///
+/// let mut header = FancyHeader::new();
+/// header.head_left("Quarterly Report").foot_center(r"\thepage");
+///
+/// let mut doc = Document::new(DocumentClass::Report);
+/// doc.preamble.fancy_header(&header);
+/// ```
+///
+/// This will add the following lines to the preamble:
+///
+/// ```tex
+/// \usepackage{fancyhdr}
+/// \pagestyle{fancy}
+/// \fancyhf{}
+/// \fancyhead[L]{Quarterly Report}
+/// \fancyfoot[C]{\thepage}
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FancyHeader {
+    head_left: Option<String>,
+    head_center: Option<String>,
+    head_right: Option<String>,
+    foot_left: Option<String>,
+    foot_center: Option<String>,
+    foot_right: Option<String>,
+}
+
+impl FancyHeader {
+    /// Create an empty `FancyHeader`.
+    pub fn new() -> FancyHeader {
+        Default::default()
+    }
+
+    /// Set the left side of the page header.
+    pub fn head_left(&mut self, text: &str) -> &mut Self {
+        self.head_left = Some(text.to_string());
+        self
+    }
+
+    /// Set the centre of the page header.
+    pub fn head_center(&mut self, text: &str) -> &mut Self {
+        self.head_center = Some(text.to_string());
+        self
+    }
+
+    /// Set the right side of the page header.
+    pub fn head_right(&mut self, text: &str) -> &mut Self {
+        self.head_right = Some(text.to_string());
+        self
+    }
+
+    /// Set the left side of the page footer.
+    pub fn foot_left(&mut self, text: &str) -> &mut Self {
+        self.foot_left = Some(text.to_string());
+        self
+    }
+
+    /// Set the centre of the page footer.
+    pub fn foot_center(&mut self, text: &str) -> &mut Self {
+        self.foot_center = Some(text.to_string());
+        self
+    }
+
+    /// Set the right side of the page footer.
+    pub fn foot_right(&mut self, text: &str) -> &mut Self {
+        self.foot_right = Some(text.to_string());
+        self
+    }
+
+    /// Get the lines which need to be added to the preamble (after importing
+    /// `fancyhdr`).
+    pub fn lines(&self) -> Vec<String> {
+        let fields = [
+            ("fancyhead", "L", &self.head_left),
+            ("fancyhead", "C", &self.head_center),
+            ("fancyhead", "R", &self.head_right),
+            ("fancyfoot", "L", &self.foot_left),
+            ("fancyfoot", "C", &self.foot_center),
+            ("fancyfoot", "R", &self.foot_right),
+        ];
+
+        let mut lines = vec![r"\pagestyle{fancy}".to_string(), r"\fancyhf{}".to_string()];
+        lines.extend(fields.iter().filter_map(|&(command, position, text)| {
+            text.as_ref()
+                .map(|t| format!(r"\{}[{}]{{{}}}", command, position, t))
+        }));
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iterate_over_a_document_in_a_for_loop() {
+        let mut doc = Document::new(DocumentClass::Article);
+        doc.push(Element::TitlePage).push(Element::ClearPage);
+
+        let mut elements = Vec::new();
+        for element in &doc {
+            elements.push(element.clone());
+        }
+
+        assert_eq!(elements, vec![Element::TitlePage, Element::ClearPage]);
+    }
+
+    #[test]
+    fn iterate_over_a_section_in_a_for_loop() {
+        let section = Section::with_elements("Intro", vec!["First", "Second"]);
+
+        let mut count = 0;
+        for element in &section {
+            assert!(matches!(*element, Element::Para(_)));
+            count += 1;
+        }
+
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn document_length() {
+        let mut doc = Document::new(DocumentClass::Article);
+        assert!(doc.is_empty());
+        assert_eq!(doc.len(), 0);
+
+        doc.push(Element::TitlePage).push("Hello World");
+        assert!(!doc.is_empty());
+        assert_eq!(doc.len(), 2);
+    }
+
+    #[test]
+    fn insert_an_element() {
+        let mut doc = Document::new(DocumentClass::Article);
+        doc.push(Element::TitlePage).push(Element::ClearPage);
+
+        doc.insert(1, Element::TableOfContents);
+
+        assert_eq!(
+            *doc,
+            vec![
+                Element::TitlePage,
+                Element::TableOfContents,
+                Element::ClearPage
+            ]
+        );
+    }
+
+    #[test]
+    fn remove_an_element() {
+        let mut doc = Document::new(DocumentClass::Article);
+        doc.push(Element::TitlePage).push(Element::ClearPage);
+
+        let removed = doc.remove(0);
+
+        assert_eq!(removed, Element::TitlePage);
+        assert_eq!(*doc, vec![Element::ClearPage]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn remove_out_of_bounds() {
+        let mut doc = Document::new(DocumentClass::Article);
+        doc.remove(0);
+    }
+
+    #[test]
+    fn replace_a_placeholder_in_two_sections() {
+        let mut first = Section::new("First");
+        first.push("Hello {{name}}, welcome!");
+        let mut para = Paragraph::new();
+        para.push(ParagraphElement::bold("Goodbye {{name}}"))
+            .push(" and {{name}}");
+        let second = Section::with_elements("Second", vec![para]);
+
+        let mut doc = Document::new(DocumentClass::Article);
+        doc.push(first).push(second);
+
+        assert_eq!(doc.replace_text("{{name}}", "Michael"), 3);
+
+        let mut should_be = Document::new(DocumentClass::Article);
+        let mut first = Section::new("First");
+        first.push("Hello Michael, welcome!");
+        let mut para = Paragraph::new();
+        para.push(ParagraphElement::bold("Goodbye Michael"))
+            .push(" and Michael");
+        should_be
+            .push(first)
+            .push(Section::with_elements("Second", vec![para]));
+        assert_eq!(doc, should_be);
+    }
+
+    #[test]
+    fn collect_labels_from_equations() {
+        let mut align = Align::new();
+        align
+            .push(Equation::with_label("eq:linear", "y &= mx + c"))
+            .push("y &= 2x")
+            .push(Equation::with_label("eq:quadratic", "y &= ax^2 + bx + c"));
+        let mut section = Section::new("Equations");
+        section.label("sec:equations").push(align);
+
+        let mut doc = Document::new(DocumentClass::Article);
+        doc.push(section);
+
+        assert_eq!(
+            doc.labels(),
+            vec!["sec:equations", "eq:linear", "eq:quadratic"]
+        );
+    }
+
+    #[test]
+    fn align_requires_amsmath() {
+        let mut section = Section::new("Equations");
+        section.push(Align::from("y &= mx + c"));
+        let mut doc = Document::new(DocumentClass::Article);
+        doc.push(section);
+
+        assert_eq!(doc.required_packages(), vec!["amsmath"]);
+    }
+
+    #[test]
+    fn required_packages_are_sorted_and_unique() {
+        let mut para = Paragraph::new();
+        para.push(ParagraphElement::link(
+            "https://example.com",
+            ParagraphElement::colored("blue", "here"),
+        ))
+        .push(ParagraphElement::link("https://example.org", "there"));
+        let mut doc = Document::new(DocumentClass::Article);
+        doc.push(para)
+            .push(Align::from("a &= b"))
+            .push(Align::new());
+
+        assert_eq!(
+            doc.required_packages(),
+            vec!["amsmath", "hyperref", "xcolor"]
+        );
+    }
+
+    #[test]
+    fn merge_preambles_with_an_overlapping_package() {
+        let mut doc = Document::new(DocumentClass::Article);
+        doc.preamble
+            .title("Main")
+            .use_package("amsmath")
+            .use_package("graphicx");
+
+        let mut fragment = Document::new(DocumentClass::Part);
+        fragment
+            .preamble
+            .title("Fragment")
+            .author("Michael")
+            .use_package("amsmath")
+            .use_package_with("amsmath", "fleqn")
+            .push(PreambleElement::UserDefined(
+                r"\setlength{\parskip}{1em}".to_string(),
+            ));
+
+        doc.merge_preamble(&fragment);
+
+        let mut should_be = Preamble::default();
+        should_be
+            .title("Main")
+            .author("Michael")
+            .use_package("amsmath")
+            .use_package("graphicx")
+            .use_package_with("amsmath", "fleqn")
+            .push(PreambleElement::UserDefined(
+                r"\setlength{\parskip}{1em}".to_string(),
+            ));
+        assert_eq!(doc.preamble, should_be);
+    }
+
+    #[test]
+    fn merge_preambles_keeps_the_date_and_sorting() {
+        let mut doc = Document::new(DocumentClass::Article);
+        doc.preamble.title("Main");
+
+        let mut fragment = Document::new(DocumentClass::Part);
+        fragment.preamble.today().sort_on_render(true);
+
+        doc.merge_preamble(&fragment);
+
+        assert_eq!(doc.preamble.date, Some(r"\today".to_string()));
+        assert!(doc.preamble.sorts_on_render());
+
+        // a date which has already been set is left alone
+        let mut other = Document::new(DocumentClass::Part);
+        other.preamble.date("1 January 2000");
+        doc.merge_preamble(&other);
+        assert_eq!(doc.preamble.date, Some(r"\today".to_string()));
+    }
+
+    #[test]
+    fn printing_a_bibliography_requires_biblatex() {
+        let mut doc = Document::new(DocumentClass::Article);
+        doc.push(Element::PrintBibliography);
+
+        assert_eq!(doc.required_packages(), vec!["biblatex"]);
+    }
+
+    #[test]
+    fn a_bib_resource_in_the_preamble_requires_biblatex() {
+        let mut doc = Document::new(DocumentClass::Article);
+        doc.preamble.add_bib_resource("refs.bib");
+
+        assert_eq!(doc.required_packages(), vec!["biblatex"]);
+    }
+
+    #[test]
+    fn multicols_require_multicol_and_their_body_is_visited() {
+        let mut doc = Document::new(DocumentClass::Article);
+        doc.push(Element::MultiCols {
+            columns: 2,
+            body: vec![Element::Align(Align::from("a &= b"))],
+        });
+
+        assert_eq!(doc.required_packages(), vec!["amsmath", "multicol"]);
+    }
+
+    #[test]
+    fn a_tikz_picture_requires_tikz() {
+        let mut doc = Document::new(DocumentClass::Article);
+        doc.push(Element::TikzPicture {
+            options: None,
+            body: r"\draw (0,0) -- (1,1);".to_string(),
+        });
+
+        assert_eq!(doc.required_packages(), vec!["tikz"]);
+    }
+
+    #[test]
+    fn hypersetup_in_the_preamble_requires_hyperref() {
+        let mut doc = Document::new(DocumentClass::Article);
+        doc.preamble.hypersetup(&[("colorlinks", "true")]);
+
+        assert_eq!(doc.required_packages(), vec!["hyperref"]);
     }
-  }
 }
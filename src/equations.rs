@@ -1,5 +1,7 @@
-use std::ops::Deref;
-use std::slice::Iter;
+use std::fmt::{self, Display, Formatter};
+use std::slice::{Iter, IterMut};
+
+use paragraph::ParagraphElement;
 
 /// A single equation.
 ///
@@ -32,6 +34,7 @@ use std::slice::Iter;
 pub struct Equation {
     text: String,
     label: Option<String>,
+    tag: Option<String>,
     not_numbered: bool,
 }
 
@@ -41,6 +44,7 @@ impl Equation {
         Equation {
             text: src.as_ref().to_string(),
             label: None,
+            tag: None,
             not_numbered: false,
         }
     }
@@ -60,6 +64,15 @@ impl Equation {
         self
     }
 
+    /// Override the equation's number with a custom tag (using `\tag`).
+    ///
+    /// A tagged equation is always given a visible number, even if it has
+    /// been marked as `not_numbered()`.
+    pub fn tag(&mut self, t: &str) -> &mut Self {
+        self.tag = Some(t.to_string());
+        self
+    }
+
     /// Set the equation's text.
     pub fn text(&mut self, src: &str) -> &mut Self {
         self.text = src.to_string();
@@ -73,6 +86,29 @@ impl Equation {
         self
     }
 
+    /// Consume the equation, returning a copy with the provided label.
+    ///
+    /// This is an owned alternative to `label()`, handy for creating an
+    /// equation in a single expression.
+    ///
+    /// ```rust
+    /// # use latex::Equation;
+    /// let eq = Equation::new("x = y").labeled("eq:1").unnumbered();
+    /// assert_eq!(eq.get_label(), Some("eq:1"));
+    /// ```
+    pub fn labeled(mut self, name: &str) -> Equation {
+        self.label(name);
+        self
+    }
+
+    /// Consume the equation, returning a copy which isn't numbered.
+    ///
+    /// This is an owned alternative to `not_numbered()`.
+    pub fn unnumbered(mut self) -> Equation {
+        self.not_numbered();
+        self
+    }
+
     /// Get the equation's text.
     pub fn get_text(&self) -> &str {
         &self.text
@@ -80,13 +116,40 @@ impl Equation {
 
     /// Get the equation label, if there is one.
     pub fn get_label(&self) -> Option<&str> {
-        self.label.as_ref().map(Deref::deref)
+        self.label.as_deref()
+    }
+
+    /// Get the equation's tag, if there is one.
+    pub fn get_tag(&self) -> Option<&str> {
+        self.tag.as_deref()
     }
 
     /// Is this equation numbered?
     pub fn is_numbered(&self) -> bool {
         !self.not_numbered
     }
+
+    /// Does this equation contain an `&` alignment point? Escaped ampersands
+    /// (`\&`) aren't counted, but an `&` straight after a `\\` row break
+    /// is.
+    pub fn is_aligned(&self) -> bool {
+        let bytes = self.text.as_bytes();
+        let mut i = 0;
+
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\\' => {
+                    let run = backslash_run(&bytes[i..]);
+                    // an odd number of backslashes escapes the next character
+                    i += run + run % 2;
+                }
+                b'&' => return true,
+                _ => i += 1,
+            }
+        }
+
+        false
+    }
 }
 
 /// A list of equations to be used in an `align` environment.
@@ -96,6 +159,11 @@ impl Equation {
 /// Using this environment requires you to include the `amsmath` package in
 /// your preamble.
 ///
+/// The equations can also be rendered in a different environment (e.g.
+/// `gather`) by creating the `Align` with `Align::with_environment()`. Keep in
+/// mind that the `&` alignment separators only make sense in an `align`
+/// environment.
+///
 ///
 /// # Examples
 ///
@@ -106,6 +174,7 @@ impl Equation {
 /// use latex::{Align, Equation};
 /// let mut equations = Align::new();
 /// equations.push("y &= mx + c")
+///          .push_intertext("and the most famous equation of all,")
 ///          .push(Equation::with_label("emc2", "E &= m c^2"));
 /// ```
 ///
@@ -114,12 +183,17 @@ impl Equation {
 /// ```tex
 /// \begin{align}
 /// y &= mx + c \\
+/// \intertext{and the most famous equation of all,}
 /// E &= m c^2 \\
 /// \end{align}
 /// ```
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Align {
-    items: Vec<Equation>,
+    environment: MathEnvironment,
+    not_numbered: bool,
+    subequations: bool,
+    no_trailing_backslash: bool,
+    items: Vec<AlignItem>,
 }
 
 impl Align {
@@ -128,16 +202,363 @@ impl Align {
         Default::default()
     }
 
-    /// Iterate over each of this equations in the list.
-    pub fn iter(&self) -> Iter<Equation> {
+    /// Create an empty equation list which will be rendered in the specified
+    /// environment.
+    pub fn with_environment(environment: MathEnvironment) -> Align {
+        Align {
+            environment,
+            ..Default::default()
+        }
+    }
+
+    /// The environment the equations are rendered in.
+    pub fn environment(&self) -> MathEnvironment {
+        self.environment
+    }
+
+    /// Render the equations without any numbering, using the starred form of
+    /// the environment (e.g. `align*`).
+    pub fn unnumbered(&mut self) -> &mut Self {
+        self.not_numbered = true;
+        self
+    }
+
+    /// Are the equations in this list numbered?
+    pub fn is_numbered(&self) -> bool {
+        !self.not_numbered
+    }
+
+    /// Set whether the equations should be grouped in a `subequations`
+    /// environment, so they are numbered 1a, 1b, 1c, etc.
+    pub fn subequations(&mut self, yes: bool) -> &mut Self {
+        self.subequations = yes;
+        self
+    }
+
+    /// Are the equations grouped in a `subequations` environment?
+    pub fn uses_subequations(&self) -> bool {
+        self.subequations
+    }
+
+    /// Set whether the last equation should also be terminated with a `\\`.
+    ///
+    /// This is enabled by default.
+    pub fn trailing_backslash(&mut self, yes: bool) -> &mut Self {
+        self.no_trailing_backslash = !yes;
+        self
+    }
+
+    /// Is the last equation terminated with a `\\`?
+    pub fn uses_trailing_backslash(&self) -> bool {
+        !self.no_trailing_backslash
+    }
+
+    /// Iterate over each of the equations in the list, skipping any
+    /// `\intertext`.
+    pub fn iter(&self) -> Equations<'_> {
+        Equations {
+            items: self.items.iter(),
+        }
+    }
+
+    /// Mutably iterate over each of the equations in the list, skipping any
+    /// `\intertext`.
+    pub fn iter_mut(&mut self) -> EquationsMut<'_> {
+        EquationsMut {
+            items: self.items.iter_mut(),
+        }
+    }
+
+    /// Iterate over each of the items (equations and `\intertext`) in the
+    /// list.
+    pub fn items(&self) -> Iter<'_, AlignItem> {
         self.items.iter()
     }
 
-    /// Add an equation to the end of the list.
-    pub fn push<E: Into<Equation>>(&mut self, eq: E) -> &mut Self {
+    /// Mutably iterate over each of the items (equations and `\intertext`)
+    /// in the list.
+    pub fn items_mut(&mut self) -> IterMut<'_, AlignItem> {
+        self.items.iter_mut()
+    }
+
+    /// The number of equations in the list, not counting any `\intertext`.
+    /// Use `items().len()` to include them.
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Does this list contain no equations?
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Add an equation (or any other `AlignItem`) to the end of the list.
+    pub fn push<E: Into<AlignItem>>(&mut self, eq: E) -> &mut Self {
         self.items.push(eq.into());
         self
     }
+
+    /// Add a short piece of text between equations, using `\intertext`.
+    pub fn push_intertext(&mut self, text: &str) -> &mut Self {
+        self.push(AlignItem::InterText(text.to_string()))
+    }
+}
+
+impl<'a> IntoIterator for &'a Align {
+    type Item = &'a Equation;
+    type IntoIter = Equations<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the equations in an `Align`, created by `Align::iter()`.
+#[derive(Clone, Debug)]
+pub struct Equations<'a> {
+    items: Iter<'a, AlignItem>,
+}
+
+impl<'a> Iterator for Equations<'a> {
+    type Item = &'a Equation;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.items.find_map(|item| match *item {
+            AlignItem::Equation(ref eq) => Some(eq),
+            AlignItem::InterText(_) => None,
+        })
+    }
+}
+
+/// A mutable iterator over the equations in an `Align`, created by
+/// `Align::iter_mut()`.
+#[derive(Debug)]
+pub struct EquationsMut<'a> {
+    items: IterMut<'a, AlignItem>,
+}
+
+impl<'a> Iterator for EquationsMut<'a> {
+    type Item = &'a mut Equation;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.items.find_map(|item| match *item {
+            AlignItem::Equation(ref mut eq) => Some(eq),
+            AlignItem::InterText(_) => None,
+        })
+    }
+}
+
+/// A single item in an `Align` block.
+///
+/// For convenience, anything which can be converted into an `Equation` can
+/// also be converted into an `AlignItem`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AlignItem {
+    /// An equation.
+    Equation(Equation),
+    /// Text interleaved between the equations, rendered using `\intertext`.
+    InterText(String),
+}
+
+impl<E: Into<Equation>> From<E> for AlignItem {
+    fn from(other: E) -> AlignItem {
+        AlignItem::Equation(other.into())
+    }
+}
+
+/// Split a block of existing LaTeX, where each equation is terminated by
+/// `\\`, into an `Align`.
+///
+/// This is handy when migrating existing `.tex` snippets. Each equation's
+/// text is copied as-is (minus surrounding whitespace) and blank lines are
+/// skipped.
+///
+/// # Examples
+///
+/// ```rust
+/// use latex::parse_align;
+///
+/// let align = parse_align(r"
+///     y &= mx + c \\
+///     y &= ax^2 + bx + c \\
+/// ");
+///
+/// assert_eq!(align.iter().count(), 2);
+/// assert!(align.iter().all(|eq| eq.is_aligned()));
+/// ```
+pub fn parse_align(src: &str) -> Align {
+    let mut align = Align::new();
+
+    for line in split_rows(src) {
+        let line = line.trim();
+        if !line.is_empty() {
+            align.push(line);
+        }
+    }
+
+    align
+}
+
+/// The number of consecutive backslashes at the start of `bytes`.
+fn backslash_run(bytes: &[u8]) -> usize {
+    bytes.iter().take_while(|&&b| b == b'\\').count()
+}
+
+/// Split some TeX on its `\\` row breaks, dropping the `*` and optional
+/// `[length]` which may follow each one.
+///
+/// Backslashes are counted in pairs, so `\\\&` is a row break followed by
+/// an escaped `&` rather than a break after `\\\`.
+fn split_rows(src: &str) -> Vec<&str> {
+    let bytes = src.as_bytes();
+    let mut rows = Vec::new();
+    let mut start = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] != b'\\' {
+            i += 1;
+            continue;
+        }
+
+        let run = backslash_run(&bytes[i..]);
+        for pair in 0..run / 2 {
+            let end = i + 2 * pair;
+            rows.push(&src[start..end]);
+            start = end + 2;
+        }
+
+        if run % 2 == 1 {
+            // the last backslash escapes whatever comes next
+            i += run + 1;
+            continue;
+        }
+
+        i = skip_row_break_arguments(bytes, i + run);
+        start = i;
+    }
+
+    if start < bytes.len() {
+        rows.push(&src[start..]);
+    }
+
+    rows
+}
+
+/// Skip past the `*` and `[length]` arguments of a `\\` which ends just
+/// before `i`, returning the index of the next row's text.
+fn skip_row_break_arguments(bytes: &[u8], mut i: usize) -> usize {
+    let skip_whitespace = |mut i: usize| {
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        i
+    };
+
+    let mut next = skip_whitespace(i);
+    if bytes.get(next) == Some(&b'*') {
+        i = next + 1;
+        next = skip_whitespace(i);
+    }
+    if bytes.get(next) == Some(&b'[') {
+        if let Some(close) = bytes[next..].iter().position(|&b| b == b']') {
+            i = next + close + 1;
+        }
+    }
+
+    i
+}
+
+/// A single long equation broken over several lines using a `split`
+/// environment nested inside an `equation`.
+///
+/// Unlike an `Align`, the whole thing only gets a single equation number.
+///
+/// # Note
+///
+/// Using this environment requires you to include the `amsmath` package in
+/// your preamble.
+///
+/// # Examples
+///
+/// ```rust
+/// use latex::SplitEquation;
+///
+/// let mut eq = SplitEquation::new();
+/// eq.push("(a + b)^2 &= (a + b)(a + b)")
+///   .push("&= a^2 + 2ab + b^2")
+///   .label("eq:square");
+/// ```
+///
+/// When rendered, you should get something like this:
+///
+/// ```tex
+/// \begin{equation}
+/// \begin{split}
+/// (a + b)^2 &= (a + b)(a + b) \\
+/// &= a^2 + 2ab + b^2
+/// \end{split}
+/// \label{eq:square}
+/// \end{equation}
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SplitEquation {
+    /// The equation's lines, typically aligned using `&`.
+    pub lines: Vec<String>,
+    /// An optional label so the equation can be referenced.
+    pub label: Option<String>,
+}
+
+impl SplitEquation {
+    /// Create an empty `SplitEquation`.
+    pub fn new() -> SplitEquation {
+        Default::default()
+    }
+
+    /// Add a line to the end of the equation.
+    pub fn push(&mut self, line: &str) -> &mut Self {
+        self.lines.push(line.to_string());
+        self
+    }
+
+    /// Give the equation a label.
+    pub fn label(&mut self, name: &str) -> &mut Self {
+        self.label = Some(name.to_string());
+        self
+    }
+}
+
+/// The environment used when rendering an `Align`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum MathEnvironment {
+    /// Several equations aligned on their `&` separators.
+    #[default]
+    Align,
+    /// A single numbered equation.
+    ///
+    /// Printing an `Align` with more than one equation in this environment
+    /// is an error.
+    Equation,
+    /// Several centered equations, without any alignment.
+    Gather,
+    /// A single long equation split over multiple lines, where each
+    /// `Equation` is one line.
+    ///
+    /// The whole block gets a single number, so printing it fails if more
+    /// than one line has a label (or a tag).
+    Multline,
+}
+
+impl MathEnvironment {
+    /// Get the `MathEnvironment`'s environment name.
+    pub fn environment_name(&self) -> &str {
+        match *self {
+            MathEnvironment::Align => "align",
+            MathEnvironment::Equation => "equation",
+            MathEnvironment::Gather => "gather",
+            MathEnvironment::Multline => "multline",
+        }
+    }
 }
 
 impl<'a> From<&'a str> for Equation {
@@ -154,3 +575,340 @@ impl<'a> From<&'a str> for Align {
         eq
     }
 }
+
+/// The kind of brackets placed around a `Matrix`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum MatrixKind {
+    /// No brackets (`matrix`).
+    #[default]
+    Plain,
+    /// Round brackets (`pmatrix`).
+    Parentheses,
+    /// Square brackets (`bmatrix`).
+    Brackets,
+    /// Curly braces (`Bmatrix`).
+    Braces,
+    /// Vertical lines, as used for determinants (`vmatrix`).
+    Vertical,
+}
+
+impl MatrixKind {
+    /// Get the `MatrixKind`'s environment name.
+    pub fn environment_name(&self) -> &str {
+        match *self {
+            MatrixKind::Plain => "matrix",
+            MatrixKind::Parentheses => "pmatrix",
+            MatrixKind::Brackets => "bmatrix",
+            MatrixKind::Braces => "Bmatrix",
+            MatrixKind::Vertical => "vmatrix",
+        }
+    }
+}
+
+/// A matrix, to be used inside a mathematical expression.
+///
+/// # Note
+///
+/// Using matrices requires you to include the `amsmath` package in your
+/// preamble.
+///
+/// # Examples
+///
+/// A `Matrix` is rendered using its `Display` impl, so it can be embedded in
+/// an `Equation` or any other bit of math.
+///
+/// ```rust
+/// use latex::{Equation, Matrix, MatrixKind};
+///
+/// let mut identity = Matrix::new(MatrixKind::Brackets);
+/// identity.push_row(&["1", "0"]).push_row(&["0", "1"]);
+///
+/// let eq = Equation::new(format!("I = {}", identity));
+/// ```
+///
+/// The matrix would get rendered to something like this:
+///
+/// ```tex
+/// \begin{bmatrix}
+/// 1 & 0 \\
+/// 0 & 1 \\
+/// \end{bmatrix}
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Matrix {
+    /// The kind of brackets placed around the matrix.
+    pub kind: MatrixKind,
+    rows: Vec<Vec<String>>,
+}
+
+impl Matrix {
+    /// Create an empty matrix of the specified kind.
+    pub fn new(kind: MatrixKind) -> Matrix {
+        Matrix {
+            kind,
+            rows: Vec::new(),
+        }
+    }
+
+    /// Add a row to the bottom of the matrix.
+    pub fn push_row<I>(&mut self, row: I) -> &mut Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.rows
+            .push(row.into_iter().map(|s| s.as_ref().to_string()).collect());
+        self
+    }
+
+    /// Iterate over the rows in the matrix.
+    pub fn iter(&self) -> Iter<'_, Vec<String>> {
+        self.rows.iter()
+    }
+}
+
+impl Display for Matrix {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let env = self.kind.environment_name();
+
+        writeln!(f, r"\begin{{{}}}", env)?;
+
+        for row in &self.rows {
+            writeln!(f, r"{} \\", row.join(" & "))?;
+        }
+
+        write!(f, r"\end{{{}}}", env)
+    }
+}
+
+/// A piecewise definition using the `cases` environment.
+///
+/// # Note
+///
+/// Using this environment requires you to include the `amsmath` package in
+/// your preamble.
+///
+/// # Examples
+///
+/// Like a `Matrix`, `Cases` is rendered using its `Display` impl so it can
+/// be embedded in an `Equation`. It can also be converted directly into an
+/// inline `ParagraphElement`.
+///
+/// ```rust
+/// use latex::{Cases, Equation};
+///
+/// let mut abs = Cases::new();
+/// abs.push("x", r"x \geq 0").push("-x", "x < 0");
+///
+/// let eq = Equation::new(format!("|x| = {}", abs));
+/// ```
+///
+/// The piecewise function would get rendered to something like this:
+///
+/// ```tex
+/// \begin{cases}
+/// x & x \geq 0 \\
+/// -x & x < 0 \\
+/// \end{cases}
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Cases {
+    branches: Vec<(String, String)>,
+}
+
+impl Cases {
+    /// Create an empty piecewise definition.
+    pub fn new() -> Cases {
+        Default::default()
+    }
+
+    /// Add a branch, consisting of an expression and the condition under
+    /// which it applies.
+    pub fn push<E, C>(&mut self, expr: E, cond: C) -> &mut Self
+    where
+        E: AsRef<str>,
+        C: AsRef<str>,
+    {
+        self.branches
+            .push((expr.as_ref().to_string(), cond.as_ref().to_string()));
+        self
+    }
+
+    /// Iterate over the `(expression, condition)` pairs.
+    pub fn iter(&self) -> Iter<'_, (String, String)> {
+        self.branches.iter()
+    }
+}
+
+impl Display for Cases {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(f, r"\begin{{cases}}")?;
+
+        for (expr, cond) in &self.branches {
+            writeln!(f, r"{} & {} \\", expr, cond)?;
+        }
+
+        write!(f, r"\end{{cases}}")
+    }
+}
+
+impl From<Cases> for ParagraphElement {
+    /// Embed the piecewise definition in an inline math expression.
+    fn from(other: Cases) -> Self {
+        ParagraphElement::InlineMath(other.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_2x2_bmatrix() {
+        let should_be = r"\begin{bmatrix}
+a & b \\
+c & d \\
+\end{bmatrix}";
+
+        let mut matrix = Matrix::new(MatrixKind::Brackets);
+        matrix.push_row(&["a", "b"]).push_row(&["c", "d"]);
+
+        assert_eq!(matrix.to_string(), should_be);
+    }
+
+    #[test]
+    fn render_empty_matrix() {
+        let matrix = Matrix::new(MatrixKind::Vertical);
+
+        assert_eq!(matrix.to_string(), "\\begin{vmatrix}\n\\end{vmatrix}");
+    }
+
+    #[test]
+    fn render_two_branch_cases() {
+        let should_be = r"\begin{cases}
+x & x \geq 0 \\
+-x & x < 0 \\
+\end{cases}";
+
+        let mut cases = Cases::new();
+        cases.push("x", r"x \geq 0").push("-x", "x < 0");
+
+        assert_eq!(cases.to_string(), should_be);
+    }
+
+    #[test]
+    fn cases_as_inline_math() {
+        let mut cases = Cases::new();
+        cases.push("1", "x > 0");
+
+        let element = ParagraphElement::from(cases.clone());
+
+        assert_eq!(element, ParagraphElement::InlineMath(cases.to_string()));
+    }
+
+    #[test]
+    fn detect_alignment_points() {
+        assert!(Equation::new("y &= mx + c").is_aligned());
+        assert!(!Equation::new("y = mx + c").is_aligned());
+        assert!(!Equation::new(r"a \& b").is_aligned());
+    }
+
+    #[test]
+    fn ampersand_after_a_row_break_is_an_alignment_point() {
+        assert!(Equation::new(r"a + b \\& = c").is_aligned());
+        assert!(!Equation::new(r"a + b \\\& c").is_aligned());
+    }
+
+    #[test]
+    fn parse_align_strips_row_break_spacing() {
+        let src = r"a &= b \\[2pt]
+        c &= d \\*
+        e &= f \\ [1em] g &= h \\";
+
+        let align = parse_align(src);
+        let equations: Vec<&str> = align.iter().map(|eq| eq.get_text()).collect();
+
+        assert_eq!(equations, vec!["a &= b", "c &= d", "e &= f", "g &= h"]);
+    }
+
+    #[test]
+    fn parse_align_keeps_escaped_characters() {
+        let align = parse_align(r"a \& b &= c \\\& d &= e");
+        let equations: Vec<&str> = align.iter().map(|eq| eq.get_text()).collect();
+
+        assert_eq!(equations, vec![r"a \& b &= c", r"\& d &= e"]);
+    }
+
+    #[test]
+    fn parse_two_line_aligned_block() {
+        let src = r"f(x) &= x^2 + 2x + 1 \\
+        &= (x + 1)^2";
+
+        let align = parse_align(src);
+        let equations: Vec<&str> = align.iter().map(|eq| eq.get_text()).collect();
+
+        assert_eq!(equations, vec!["f(x) &= x^2 + 2x + 1", "&= (x + 1)^2"]);
+        assert!(align.iter().all(Equation::is_aligned));
+    }
+
+    #[test]
+    fn chained_equation_construction() {
+        let eq = Equation::new("x = y").labeled("eq:1").unnumbered();
+
+        assert_eq!(eq.get_text(), "x = y");
+        assert_eq!(eq.get_label(), Some("eq:1"));
+        assert!(!eq.is_numbered());
+
+        let mut should_be = Equation::new("x = y");
+        should_be.label("eq:1").not_numbered();
+        assert_eq!(eq, should_be);
+    }
+
+    #[test]
+    fn owned_builders_can_be_used_in_any_order() {
+        let eq = Equation::new("a &= b").unnumbered().labeled("eq:2");
+
+        assert_eq!(eq.get_label(), Some("eq:2"));
+        assert!(!eq.is_numbered());
+    }
+
+    #[test]
+    fn equations_skip_intertext() {
+        let mut align = Align::new();
+        align
+            .push("a &= b")
+            .push_intertext("and")
+            .push(Equation::new("c &= d"));
+
+        assert_eq!(align.items().count(), 3);
+        let equations: Vec<&str> = align.iter().map(|eq| eq.get_text()).collect();
+        assert_eq!(equations, vec!["a &= b", "c &= d"]);
+    }
+
+    #[test]
+    fn iterate_over_an_align_in_a_for_loop() {
+        let mut align = Align::new();
+        align.push("a &= b").push_intertext("and").push("c &= d");
+
+        let mut equations = Vec::new();
+        for equation in &align {
+            equations.push(equation.get_text());
+        }
+
+        assert_eq!(equations, vec!["a &= b", "c &= d"]);
+    }
+
+    #[test]
+    fn align_length() {
+        let mut align = Align::new();
+        assert!(align.is_empty());
+
+        align.push_intertext("and");
+        assert!(align.is_empty());
+
+        align.push("a &= b").push("c &= d");
+        assert!(!align.is_empty());
+        assert_eq!(align.len(), 2);
+        assert_eq!(align.items().len(), 3);
+    }
+}
@@ -0,0 +1,198 @@
+use std::fmt::{self, Display, Formatter};
+
+/// An image to be included in the document using `\includegraphics`.
+///
+/// # Note
+///
+/// Including images requires you to include the `graphicx` package in your
+/// preamble.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Image {
+    /// The path to the image file.
+    pub path: String,
+    /// The width the image should be scaled to (e.g. `0.5\textwidth`).
+    pub width: Option<String>,
+    /// The height the image should be scaled to (e.g. `4cm`).
+    pub height: Option<String>,
+    /// A factor to scale the image by.
+    pub scale: Option<f64>,
+}
+
+impl Image {
+    /// Create a new `Image` pointing at the specified file.
+    pub fn new(path: &str) -> Image {
+        Image {
+            path: path.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the image's width.
+    pub fn width(&mut self, width: &str) -> &mut Self {
+        self.width = Some(width.to_string());
+        self
+    }
+
+    /// Set the image's height.
+    pub fn height(&mut self, height: &str) -> &mut Self {
+        self.height = Some(height.to_string());
+        self
+    }
+
+    /// Set the factor the image is scaled by.
+    pub fn scale(&mut self, scale: f64) -> &mut Self {
+        self.scale = Some(scale);
+        self
+    }
+
+    /// Get the options passed to `\includegraphics` (e.g. `width=4cm`).
+    pub fn options(&self) -> Vec<String> {
+        let mut options = Vec::new();
+
+        if let Some(ref width) = self.width {
+            options.push(format!("width={}", width));
+        }
+        if let Some(ref height) = self.height {
+            options.push(format!("height={}", height));
+        }
+        if let Some(scale) = self.scale {
+            options.push(format!("scale={}", scale));
+        }
+
+        options
+    }
+}
+
+/// A `figure` environment containing a centered image, with an optional
+/// caption and label.
+///
+/// # Note
+///
+/// Including images requires you to include the `graphicx` package in your
+/// preamble.
+///
+/// # Examples
+///
+/// ```rust
+/// use latex::{Figure, Image};
+///
+/// let mut image = Image::new("diagram.png");
+/// image.width(r"0.8\textwidth");
+///
+/// let mut figure = Figure::new(image);
+/// figure.caption("An example diagram").label("fig:diagram");
+/// ```
+///
+/// This would get rendered to something like this:
+///
+/// ```tex
+/// \begin{figure}
+/// \centering
+/// \includegraphics[width=0.8\textwidth]{diagram.png}
+/// \caption{An example diagram}
+/// \label{fig:diagram}
+/// \end{figure}
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Figure {
+    /// The image being displayed.
+    pub image: Image,
+    /// Where LaTeX is allowed to place the figure.
+    pub placement: Option<Placement>,
+    /// An optional caption for the figure.
+    pub caption: Option<String>,
+    /// An optional label so the figure can be referenced.
+    pub label: Option<String>,
+}
+
+impl Figure {
+    /// Create a new `Figure` displaying the provided image.
+    pub fn new(image: Image) -> Figure {
+        Figure {
+            image,
+            ..Default::default()
+        }
+    }
+
+    /// Set where LaTeX is allowed to place the figure.
+    pub fn placement(&mut self, placement: Placement) -> &mut Self {
+        self.placement = Some(placement);
+        self
+    }
+
+    /// Set the figure's caption.
+    pub fn caption(&mut self, caption: &str) -> &mut Self {
+        self.caption = Some(caption.to_string());
+        self
+    }
+
+    /// Give the figure a label.
+    pub fn label(&mut self, name: &str) -> &mut Self {
+        self.label = Some(name.to_string());
+        self
+    }
+}
+
+impl From<Image> for Figure {
+    fn from(other: Image) -> Figure {
+        Figure::new(other)
+    }
+}
+
+/// The placement specifier for a float, controlling where LaTeX is allowed to
+/// put it (e.g. `[htbp]`).
+///
+/// # Note
+///
+/// The `exactly_here` (`H`) specifier requires you to include the `float`
+/// package in your preamble. When it is set, all other specifiers are
+/// ignored.
+///
+/// # Examples
+///
+/// ```rust
+/// use latex::Placement;
+///
+/// let placement = Placement {
+///     here: true,
+///     top: true,
+///     ..Default::default()
+/// };
+/// assert_eq!(placement.to_string(), "ht");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Placement {
+    /// Place the float approximately where it occurs in the source (`h`).
+    pub here: bool,
+    /// Place the float at the top of a page (`t`).
+    pub top: bool,
+    /// Place the float at the bottom of a page (`b`).
+    pub bottom: bool,
+    /// Place the float on a special page just for floats (`p`).
+    pub page: bool,
+    /// Place the float at precisely this location (`H`).
+    pub exactly_here: bool,
+}
+
+impl Display for Placement {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.exactly_here {
+            return write!(f, "H");
+        }
+
+        let specifiers = [
+            (self.here, 'h'),
+            (self.top, 't'),
+            (self.bottom, 'b'),
+            (self.page, 'p'),
+        ];
+
+        for &(enabled, specifier) in &specifiers {
+            if enabled {
+                write!(f, "{}", specifier)?;
+            }
+        }
+
+        Ok(())
+    }
+}
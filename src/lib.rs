@@ -108,15 +108,28 @@ extern crate failure;
 
 mod document;
 mod equations;
+mod figure;
+mod listing;
 mod lists;
 mod paragraph;
 mod section;
+mod theorem;
+mod validate;
 mod visitor;
 
-pub use document::{Document, DocumentClass, Element, Preamble, PreambleElement};
-pub use equations::{Align, Equation};
+pub use document::{
+    Document, DocumentClass, Element, FancyHeader, PageGeometry, Preamble, PreambleElement,
+};
+pub use equations::{
+    parse_align, Align, AlignItem, Cases, Equation, Equations, EquationsMut, MathEnvironment,
+    Matrix, MatrixKind, SplitEquation,
+};
+pub use figure::{Figure, Image, Placement};
+pub use listing::{Listing, MintedListing};
 pub use lists::{Item, List, ListKind};
 pub use paragraph::{Paragraph, ParagraphElement};
-pub use section::Section;
+pub use section::{Section, SectionLevel};
+pub use theorem::Theorem;
+pub use validate::{validate, ValidationWarning};
 
-pub use visitor::{print, Visitor};
+pub use visitor::{count, print, print_to, Printer, Visitor, VisitorMut, WordCount};
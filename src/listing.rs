@@ -0,0 +1,135 @@
+/// A block of source code, rendered using the `lstlisting` environment.
+///
+/// # Note
+///
+/// Using listings requires you to include the `listings` package in your
+/// preamble.
+///
+/// # Examples
+///
+/// ```rust
+/// use latex::Listing;
+///
+/// let mut listing = Listing::new("fn main() {}");
+/// listing.language("Rust").caption("An empty program");
+/// ```
+///
+/// This would get rendered to something like this:
+///
+/// ```tex
+/// \begin{lstlisting}[language={Rust},caption={An empty program}]
+/// fn main() {}
+/// \end{lstlisting}
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Listing {
+    /// The language the code is written in (e.g. `Python`).
+    pub language: Option<String>,
+    /// An optional caption for the listing.
+    pub caption: Option<String>,
+    /// An optional label so the listing can be referenced.
+    pub label: Option<String>,
+    /// The source code itself.
+    pub code: String,
+}
+
+impl Listing {
+    /// Create a new `Listing` containing the provided code.
+    pub fn new(code: &str) -> Listing {
+        Listing {
+            code: code.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the language used for syntax highlighting.
+    pub fn language(&mut self, language: &str) -> &mut Self {
+        self.language = Some(language.to_string());
+        self
+    }
+
+    /// Set the listing's caption.
+    pub fn caption(&mut self, caption: &str) -> &mut Self {
+        self.caption = Some(caption.to_string());
+        self
+    }
+
+    /// Give the listing a label.
+    pub fn label(&mut self, name: &str) -> &mut Self {
+        self.label = Some(name.to_string());
+        self
+    }
+
+    /// Get the options passed to the `lstlisting` environment (e.g.
+    /// `language={Python}`). Values are wrapped in braces so they may
+    /// contain commas.
+    pub fn options(&self) -> Vec<String> {
+        let mut options = Vec::new();
+
+        if let Some(ref language) = self.language {
+            options.push(format!("language={{{}}}", language));
+        }
+        if let Some(ref caption) = self.caption {
+            options.push(format!("caption={{{}}}", caption));
+        }
+        if let Some(ref label) = self.label {
+            options.push(format!("label={{{}}}", label));
+        }
+
+        options
+    }
+}
+
+/// A block of syntax highlighted source code, rendered using the `minted`
+/// environment.
+///
+/// # Note
+///
+/// Using `minted` requires you to include the `minted` package in your
+/// preamble. Because it calls out to [Pygments] for highlighting, the document
+/// must also be compiled with the `--shell-escape` flag.
+///
+/// # Examples
+///
+/// ```rust
+/// use latex::MintedListing;
+///
+/// let mut listing = MintedListing::new("python", "print('Hello, World!')");
+/// listing.option("linenos", "true");
+/// ```
+///
+/// This would get rendered to something like this:
+///
+/// ```tex
+/// \begin{minted}[linenos=true]{python}
+/// print('Hello, World!')
+/// \end{minted}
+/// ```
+///
+/// [Pygments]: http://pygments.org/
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MintedListing {
+    /// The language the code is written in (e.g. `python`).
+    pub language: String,
+    /// The source code itself.
+    pub code: String,
+    /// Any extra `key=value` options passed to the environment.
+    pub options: Vec<(String, String)>,
+}
+
+impl MintedListing {
+    /// Create a new `MintedListing` containing code in the specified language.
+    pub fn new(language: &str, code: &str) -> MintedListing {
+        MintedListing {
+            language: language.to_string(),
+            code: code.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Add a `key=value` option to the environment.
+    pub fn option(&mut self, key: &str, value: &str) -> &mut Self {
+        self.options.push((key.to_string(), value.to_string()));
+        self
+    }
+}
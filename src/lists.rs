@@ -1,17 +1,21 @@
-use std::ops::Deref;
-use std::slice::Iter;
+use std::iter::FromIterator;
+use std::slice::{Iter, IterMut};
 
-/// Wrapper around a single list item.
-///
-/// An item will usually be rendered with `\item` followed by the item's text.
+/// A single list item.
 #[derive(Clone, Debug, PartialEq)]
-pub struct Item(pub String);
-
-impl Deref for Item {
-    type Target = str;
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
+pub enum Item {
+    /// A normal item, rendered with `\item` followed by the item's text.
+    Text(String),
+    /// An item with a custom marker, rendered as `\item[label]` followed by
+    /// the item's text.
+    Labeled {
+        /// The marker used instead of the usual bullet or number.
+        label: String,
+        /// The item's text.
+        text: String,
+    },
+    /// A nested list.
+    Sublist(List),
 }
 
 /// Which kind of list should be used?
@@ -57,6 +61,18 @@ impl ListKind {
 /// \item Dot-points
 /// \end{itemize}
 /// ```
+///
+/// Lists can also be nested inside each other.
+///
+/// ```rust
+/// use latex::{List, ListKind};
+///
+/// let mut fruit = List::new(ListKind::Itemize);
+/// fruit.push("Apple").push("Banana");
+///
+/// let mut food = List::new(ListKind::Itemize);
+/// food.push("Fruit").push_list(fruit).push("Vegetables");
+/// ```
 #[derive(Clone, Debug, PartialEq)]
 pub struct List {
     /// The kind of list this is.
@@ -73,16 +89,92 @@ impl List {
         }
     }
 
+    /// Create a list of the specified type which already contains some
+    /// items.
+    ///
+    /// ```rust
+    /// use latex::{List, ListKind};
+    ///
+    /// let list = List::with_items(ListKind::Enumerate, &["First", "Second"]);
+    /// assert_eq!(list.iter().count(), 2);
+    /// ```
+    pub fn with_items<I>(kind: ListKind, items: I) -> List
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut list = List::new(kind);
+        for item in items {
+            list.push(item);
+        }
+        list
+    }
+
     /// Add an element to the list.
     pub fn push<S: AsRef<str>>(&mut self, item: S) -> &mut Self {
-        self.items.push(Item(item.as_ref().to_string()));
+        self.items.push(Item::Text(item.as_ref().to_string()));
+        self
+    }
+
+    /// Add an element to the list which uses a custom marker (e.g. `*` or
+    /// `Q1`).
+    pub fn push_labeled<L, S>(&mut self, label: L, item: S) -> &mut Self
+    where
+        L: AsRef<str>,
+        S: AsRef<str>,
+    {
+        self.items.push(Item::Labeled {
+            label: label.as_ref().to_string(),
+            text: item.as_ref().to_string(),
+        });
         self
     }
 
+    /// Nest another list inside this one.
+    ///
+    /// If the nested list is the first item, an empty `\item` is printed
+    /// before it because LaTeX won't start a list without one.
+    pub fn push_list(&mut self, sublist: List) -> &mut Self {
+        self.items.push(Item::Sublist(sublist));
+        self
+    }
+
+    /// The number of items in the list, counting a nested list as a single
+    /// item.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Is this list empty?
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
     /// Iterate over the items in the list.
-    pub fn iter(&self) -> Iter<Item> {
+    pub fn iter(&self) -> Iter<'_, Item> {
         self.items.iter()
     }
+
+    /// Mutably iterate over the items in the list.
+    pub fn iter_mut(&mut self) -> IterMut<'_, Item> {
+        self.items.iter_mut()
+    }
+}
+
+impl<'a> IntoIterator for &'a List {
+    type Item = &'a Item;
+    type IntoIter = Iter<'a, Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for List {
+    /// Collect some items into an `Itemize` list.
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> List {
+        List::with_items(ListKind::Itemize, iter)
+    }
 }
 
 #[cfg(test)]
@@ -97,4 +189,61 @@ mod tests {
         list.push("Hello World");
         assert_eq!(list.items.len(), 1);
     }
+
+    #[test]
+    fn push_sublist_to_list() {
+        let mut list = List::new(ListKind::Itemize);
+        list.push_list(List::new(ListKind::Enumerate));
+
+        assert_eq!(
+            list.items,
+            vec![Item::Sublist(List::new(ListKind::Enumerate))]
+        );
+    }
+
+    #[test]
+    fn create_list_with_items() {
+        let list = List::with_items(ListKind::Enumerate, vec!["a", "b"]);
+
+        assert_eq!(list.kind, ListKind::Enumerate);
+        assert_eq!(
+            list.items,
+            vec![Item::Text("a".to_string()), Item::Text("b".to_string())]
+        );
+    }
+
+    #[test]
+    fn collect_into_a_list() {
+        let list: List = vec!["a", "b"].into_iter().collect();
+        let from_strings: List = vec![String::from("a"), String::from("b")]
+            .into_iter()
+            .collect();
+
+        assert_eq!(list.kind, ListKind::Itemize);
+        assert_eq!(list, List::with_items(ListKind::Itemize, &["a", "b"]));
+        assert_eq!(from_strings, list);
+    }
+
+    #[test]
+    fn iterate_over_a_list_in_a_for_loop() {
+        let list = List::with_items(ListKind::Itemize, &["a", "b"]);
+
+        let mut items = Vec::new();
+        for item in &list {
+            items.push(item.clone());
+        }
+
+        assert_eq!(items, list.items);
+    }
+
+    #[test]
+    fn list_length() {
+        let mut list = List::new(ListKind::Itemize);
+        assert!(list.is_empty());
+
+        list.push("a")
+            .push_list(List::with_items(ListKind::Itemize, &["b", "c"]));
+        assert!(!list.is_empty());
+        assert_eq!(list.len(), 2);
+    }
 }
@@ -52,7 +52,7 @@ impl Paragraph {
     }
 
     /// Iterate over the `ParagraphElement`s in this `Paragraph`.
-    pub fn iter(&self) -> Iter<ParagraphElement> {
+    pub fn iter(&self) -> Iter<'_, ParagraphElement> {
         self.elements.iter()
     }
 }
@@ -77,11 +77,164 @@ pub enum ParagraphElement {
     Bold(Box<ParagraphElement>),
     /// Italicized text.
     Italic(Box<ParagraphElement>),
+    /// Underlined text, rendered using `\underline`.
+    ///
+    /// # Note
+    ///
+    /// `\underline` is built into LaTeX, so no extra packages are needed.
+    /// Unlike `\uline` from the `ulem` package, it can't break across lines.
+    Underline(Box<ParagraphElement>),
+    /// Struck-through text.
+    ///
+    /// # Note
+    ///
+    /// This is rendered using `\sout`, which requires the `ulem` package.
+    Strikeout(Box<ParagraphElement>),
+    /// Superscript text (outside of math mode).
+    Superscript(Box<ParagraphElement>),
+    /// Subscript text (outside of math mode).
+    Subscript(Box<ParagraphElement>),
+    /// Text in small capitals, rendered using `\textsc`.
+    SmallCaps(Box<ParagraphElement>),
+    /// Emphasized text, rendered using `\emph`.
+    ///
+    /// Unlike `Italic`, emphasis toggles back to upright text when it is
+    /// used inside something which is already italicized.
+    Emph(Box<ParagraphElement>),
+    /// Text in a monospace (typewriter) font, rendered using `\texttt`.
+    ///
+    /// Unlike `Verb`, the contents are still escaped like normal text and
+    /// may contain other formatting.
+    Monospace(Box<ParagraphElement>),
+    /// A hyperlink, rendered using `\href`.
+    ///
+    /// # Note
+    ///
+    /// Using links requires you to include the `hyperref` package in your
+    /// preamble.
+    Link {
+        /// The URL being linked to.
+        url: String,
+        /// The text displayed for the link.
+        text: Box<ParagraphElement>,
+    },
+    /// A URL displayed as-is, rendered using `\url`.
+    ///
+    /// # Note
+    ///
+    /// This requires you to include either the `hyperref` or `url` package
+    /// in your preamble.
+    Url(String),
+    /// Colored text, rendered using `\textcolor`.
+    ///
+    /// The color is passed through as-is, so it can be either a named color
+    /// (e.g. `red`) or anything else `\textcolor` accepts.
+    ///
+    /// # Note
+    ///
+    /// Using colors requires you to include the `xcolor` package in your
+    /// preamble.
+    Colored {
+        /// The color to use.
+        color: String,
+        /// The text being colored.
+        inner: Box<ParagraphElement>,
+    },
+    /// Inline verbatim text, rendered using `\verb`.
+    ///
+    /// The delimiter is chosen automatically so it doesn't clash with the
+    /// text's contents.
+    Verb(String),
+    /// A reference to a label, rendered using `\ref`.
+    Ref(String),
+    /// A reference to the page a label is on, rendered using `\pageref`.
+    Pageref(String),
+    /// A citation of one or more bibliography entries, rendered using
+    /// `\cite`.
+    Cite {
+        /// The keys of the entries being cited.
+        keys: Vec<String>,
+        /// An optional note placed before the citation.
+        pre_note: Option<String>,
+        /// An optional note placed after the citation (e.g. a page number).
+        post_note: Option<String>,
+    },
+    /// An English ordinal number with a superscripted suffix (e.g.
+    /// `1\textsuperscript{st}`).
+    Ordinal(u64),
+    /// Horizontal space, rendered using `\hspace` (e.g. `1cm` or `\fill`).
+    HSpace(String),
+    /// A space which never breaks across lines (`~`), e.g. between "Figure"
+    /// and its number.
+    NonBreakingSpace,
+    /// A thin space (`\,`), e.g. between a number and its unit.
+    ThinSpace,
+    /// Force a line break without starting a new paragraph (`\\`).
+    LineBreak,
+    /// Force a line break followed by some extra vertical space (e.g.
+    /// `\\[1em]`).
+    LineBreakWith(String),
     /// An inline mathematical expression.
     InlineMath(String),
+    /// An unnumbered, centered mathematical expression (`\[ ... \]`).
+    DisplayMath(String),
 }
 
 impl ParagraphElement {
+    /// The element wrapped by formatting like `Bold` or `Link`, if there is
+    /// one.
+    ///
+    /// This lets visitors recurse into nested formatting without needing to
+    /// know about every variant.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use latex::ParagraphElement;
+    ///
+    /// let bold = ParagraphElement::bold("Hello");
+    /// assert_eq!(bold.children(), Some(&ParagraphElement::from("Hello")));
+    /// assert_eq!(ParagraphElement::from("World").children(), None);
+    /// ```
+    pub fn children(&self) -> Option<&ParagraphElement> {
+        match *self {
+            ParagraphElement::Bold(ref e)
+            | ParagraphElement::Italic(ref e)
+            | ParagraphElement::Underline(ref e)
+            | ParagraphElement::Strikeout(ref e)
+            | ParagraphElement::Superscript(ref e)
+            | ParagraphElement::Subscript(ref e)
+            | ParagraphElement::SmallCaps(ref e)
+            | ParagraphElement::Emph(ref e)
+            | ParagraphElement::Monospace(ref e)
+            | ParagraphElement::Link { text: ref e, .. }
+            | ParagraphElement::Colored { inner: ref e, .. } => Some(e),
+            _ => None,
+        }
+    }
+
+    /// The mutable counterpart to `children()`.
+    pub fn children_mut(&mut self) -> Option<&mut ParagraphElement> {
+        match *self {
+            ParagraphElement::Bold(ref mut e)
+            | ParagraphElement::Italic(ref mut e)
+            | ParagraphElement::Underline(ref mut e)
+            | ParagraphElement::Strikeout(ref mut e)
+            | ParagraphElement::Superscript(ref mut e)
+            | ParagraphElement::Subscript(ref mut e)
+            | ParagraphElement::SmallCaps(ref mut e)
+            | ParagraphElement::Emph(ref mut e)
+            | ParagraphElement::Monospace(ref mut e)
+            | ParagraphElement::Link {
+                text: ref mut e, ..
+            }
+            | ParagraphElement::Colored {
+                inner: ref mut e, ..
+            } => Some(e),
+            _ => None,
+        }
+    }
+
     /// Convenience method for wrapping a `ParagraphElement` in an italics tag.
     pub fn italic<E>(elem: E) -> ParagraphElement
     where
@@ -97,6 +250,121 @@ impl ParagraphElement {
     {
         ParagraphElement::Bold(Box::new(elem.into()))
     }
+
+    /// Convenience method for making a `ParagraphElement` both bold and
+    /// italic.
+    pub fn bold_italic<E>(elem: E) -> ParagraphElement
+    where
+        E: Into<ParagraphElement>,
+    {
+        ParagraphElement::bold(ParagraphElement::italic(elem))
+    }
+
+    /// Convenience method for wrapping a `ParagraphElement` in an underline
+    /// tag.
+    pub fn underline<E>(elem: E) -> ParagraphElement
+    where
+        E: Into<ParagraphElement>,
+    {
+        ParagraphElement::Underline(Box::new(elem.into()))
+    }
+
+    /// Convenience method for striking out a `ParagraphElement`.
+    pub fn strikeout<E>(elem: E) -> ParagraphElement
+    where
+        E: Into<ParagraphElement>,
+    {
+        ParagraphElement::Strikeout(Box::new(elem.into()))
+    }
+
+    /// Convenience method for wrapping a `ParagraphElement` in a superscript.
+    pub fn superscript<E>(elem: E) -> ParagraphElement
+    where
+        E: Into<ParagraphElement>,
+    {
+        ParagraphElement::Superscript(Box::new(elem.into()))
+    }
+
+    /// Convenience method for wrapping a `ParagraphElement` in a subscript.
+    pub fn subscript<E>(elem: E) -> ParagraphElement
+    where
+        E: Into<ParagraphElement>,
+    {
+        ParagraphElement::Subscript(Box::new(elem.into()))
+    }
+
+    /// Convenience method for wrapping a `ParagraphElement` in small caps.
+    pub fn small_caps<E>(elem: E) -> ParagraphElement
+    where
+        E: Into<ParagraphElement>,
+    {
+        ParagraphElement::SmallCaps(Box::new(elem.into()))
+    }
+
+    /// Convenience method for emphasizing a `ParagraphElement`.
+    pub fn emph<E>(elem: E) -> ParagraphElement
+    where
+        E: Into<ParagraphElement>,
+    {
+        ParagraphElement::Emph(Box::new(elem.into()))
+    }
+
+    /// Convenience method for wrapping a `ParagraphElement` in a monospace
+    /// font.
+    pub fn monospace<E>(elem: E) -> ParagraphElement
+    where
+        E: Into<ParagraphElement>,
+    {
+        ParagraphElement::Monospace(Box::new(elem.into()))
+    }
+
+    /// Convenience method for creating a hyperlink to `url` which displays
+    /// the provided text.
+    pub fn link<E>(url: &str, text: E) -> ParagraphElement
+    where
+        E: Into<ParagraphElement>,
+    {
+        ParagraphElement::Link {
+            url: url.to_string(),
+            text: Box::new(text.into()),
+        }
+    }
+
+    /// Convenience method for displaying a URL.
+    pub fn url(url: &str) -> ParagraphElement {
+        ParagraphElement::Url(url.to_string())
+    }
+
+    /// Convenience method for coloring a `ParagraphElement`.
+    pub fn colored<E>(color: &str, inner: E) -> ParagraphElement
+    where
+        E: Into<ParagraphElement>,
+    {
+        ParagraphElement::Colored {
+            color: color.to_string(),
+            inner: Box::new(inner.into()),
+        }
+    }
+
+    /// Convenience method for writing an ordinal number (e.g. "1st" or
+    /// "22nd").
+    pub fn ordinal(n: u64) -> ParagraphElement {
+        ParagraphElement::Ordinal(n)
+    }
+
+    /// Convenience method for referencing a label.
+    pub fn reference(label: &str) -> ParagraphElement {
+        ParagraphElement::Ref(label.to_string())
+    }
+
+    /// Convenience method for citing one or more bibliography entries.
+    pub fn cite(keys: &[&str]) -> ParagraphElement {
+        ParagraphElement::Cite {
+            keys: keys.iter().map(|k| k.to_string()).collect(),
+            pre_note: None,
+            post_note: None,
+        }
+    }
 }
 
 impl<'a> From<&'a str> for ParagraphElement {
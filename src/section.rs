@@ -1,4 +1,4 @@
-use std::slice::Iter;
+use std::slice::{Iter, IterMut};
 
 use document::Element;
 
@@ -7,10 +7,14 @@ use document::Element;
 /// Like the `Document` type, a `Section` is more or less just a collection of
 /// `Element`s. When rendered it will start with `\section{Section Name}` and
 /// then each element will be rendered in turn.
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Section {
     /// The name of the section.
     pub name: String,
+    /// How deeply nested this section is.
+    pub level: SectionLevel,
+    label: Option<String>,
+    numbered: bool,
     elements: Vec<Element>,
 }
 
@@ -23,6 +27,48 @@ impl Section {
         }
     }
 
+    /// Create a new section which already contains some elements.
+    pub fn with_elements<I>(name: &str, elements: I) -> Section
+    where
+        I: IntoIterator,
+        I::Item: Into<Element>,
+    {
+        let mut section = Section::new(name);
+        for element in elements {
+            section.push(element);
+        }
+        section
+    }
+
+    /// Set whether this section is numbered. Unnumbered sections are rendered
+    /// using the starred form (e.g. `\section*{...}`).
+    pub fn numbered(&mut self, yes: bool) -> &mut Self {
+        self.numbered = yes;
+        self
+    }
+
+    /// Set the section's level (e.g. to turn it into a `\subsection`).
+    pub fn level(&mut self, level: SectionLevel) -> &mut Self {
+        self.level = level;
+        self
+    }
+
+    /// Give the section a label so it can be referenced elsewhere.
+    pub fn label(&mut self, name: &str) -> &mut Self {
+        self.label = Some(name.to_string());
+        self
+    }
+
+    /// Get the section's label, if there is one.
+    pub fn get_label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Is this section numbered?
+    pub fn is_numbered(&self) -> bool {
+        self.numbered
+    }
+
     /// Add an element to the Section.
     pub fn push<I>(&mut self, element: I) -> &mut Self
     where
@@ -33,12 +79,78 @@ impl Section {
     }
 
     /// Iterate over the elements in this list.
-    pub fn iter(&self) -> Iter<Element> {
+    pub fn iter(&self) -> Iter<'_, Element> {
         self.elements.iter()
     }
 
+    /// Mutably iterate over the elements in this section.
+    pub fn iter_mut(&mut self) -> IterMut<'_, Element> {
+        self.elements.iter_mut()
+    }
+
     /// Is this section empty?
     pub fn is_empty(&self) -> bool {
         self.elements.is_empty()
     }
 }
+
+impl<'a> IntoIterator for &'a Section {
+    type Item = &'a Element;
+    type IntoIter = Iter<'a, Element>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Default for Section {
+    fn default() -> Self {
+        Section {
+            name: String::new(),
+            level: SectionLevel::default(),
+            label: None,
+            numbered: true,
+            elements: Vec::new(),
+        }
+    }
+}
+
+/// How deeply nested a `Section` is.
+///
+/// # Note
+///
+/// The `Part` and `Chapter` levels are only available with the `book` and
+/// `report` document classes.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SectionLevel {
+    /// A `\part`.
+    Part,
+    /// A `\chapter`.
+    Chapter,
+    /// A `\section`.
+    #[default]
+    Section,
+    /// A `\subsection`.
+    Subsection,
+    /// A `\subsubsection`.
+    Subsubsection,
+    /// A `\paragraph`.
+    Paragraph,
+    /// A `\subparagraph`.
+    Subparagraph,
+}
+
+impl SectionLevel {
+    /// Get the name of the command used to start a section at this level.
+    pub fn command_name(&self) -> &str {
+        match *self {
+            SectionLevel::Part => "part",
+            SectionLevel::Chapter => "chapter",
+            SectionLevel::Section => "section",
+            SectionLevel::Subsection => "subsection",
+            SectionLevel::Subsubsection => "subsubsection",
+            SectionLevel::Paragraph => "paragraph",
+            SectionLevel::Subparagraph => "subparagraph",
+        }
+    }
+}
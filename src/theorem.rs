@@ -0,0 +1,77 @@
+use std::slice::{Iter, IterMut};
+
+use document::Element;
+
+/// A theorem-like environment (e.g. a theorem, lemma, or proof).
+///
+/// # Note
+///
+/// Apart from `proof` (which comes from the `amsthm` package), the
+/// environment needs to be declared in the preamble using
+/// `Preamble::new_theorem()` before it can be used.
+///
+/// # Examples
+///
+/// ```rust
+/// use latex::{Document, DocumentClass, Theorem};
+///
+/// let mut doc = Document::new(DocumentClass::Article);
+/// doc.preamble.new_theorem("theorem", "Theorem");
+///
+/// let mut theorem = Theorem::new("theorem");
+/// theorem
+///     .title("Pythagoras")
+///     .push("In a right triangle, $a^2 + b^2 = c^2$.");
+/// doc.push(theorem);
+/// ```
+///
+/// The theorem would get rendered to something like this:
+///
+/// ```tex
+/// \begin{theorem}[Pythagoras]
+/// In a right triangle, $a^2 + b^2 = c^2$.
+/// \end{theorem}
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Theorem {
+    /// The name of the environment (e.g. `theorem` or `lemma`).
+    pub env: String,
+    /// An optional title, displayed alongside the theorem's number.
+    pub title: Option<String>,
+    elements: Vec<Element>,
+}
+
+impl Theorem {
+    /// Create a new, empty theorem using the specified environment.
+    pub fn new(env: &str) -> Theorem {
+        Theorem {
+            env: env.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the theorem's title.
+    pub fn title(&mut self, title: &str) -> &mut Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /// Add an element to the theorem's body.
+    pub fn push<I>(&mut self, element: I) -> &mut Self
+    where
+        I: Into<Element>,
+    {
+        self.elements.push(element.into());
+        self
+    }
+
+    /// Iterate over the elements in the theorem's body.
+    pub fn iter(&self) -> Iter<'_, Element> {
+        self.elements.iter()
+    }
+
+    /// Mutably iterate over the elements in the theorem's body.
+    pub fn iter_mut(&mut self) -> IterMut<'_, Element> {
+        self.elements.iter_mut()
+    }
+}
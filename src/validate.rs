@@ -0,0 +1,300 @@
+use std::collections::HashSet;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+
+use document::{Document, PreambleElement};
+use failure::Error;
+use paragraph::ParagraphElement;
+use visitor::Visitor;
+
+/// Check a document for common cross-referencing mistakes.
+///
+/// Each label is only ever reported once, regardless of how many times it
+/// was duplicated or referenced.
+///
+/// # Note
+///
+/// The keys used by `\cite` are checked against the entries in every `.bib`
+/// file added with `Preamble::add_bib_resource()`, which are read from disk
+/// relative to the current directory. A `.bib` file which can't be read is
+/// reported as a `MissingBibResource`. If no `.bib` files were added, citations
+/// aren't checked at all, because the bibliography may be written by hand
+/// (e.g. using a raw `thebibliography` environment).
+///
+/// # Examples
+///
+/// ```rust
+/// use latex::{validate, Document, DocumentClass, Paragraph, ParagraphElement,
+///             ValidationWarning};
+///
+/// let mut para = Paragraph::new();
+/// para.push("See ").push(ParagraphElement::reference("fig:missing"));
+/// let mut doc = Document::new(DocumentClass::Article);
+/// doc.push(para);
+///
+/// assert_eq!(
+///     validate(&doc),
+///     vec![ValidationWarning::DanglingRef("fig:missing".to_string())]
+/// );
+/// ```
+pub fn validate(doc: &Document) -> Vec<ValidationWarning> {
+    let mut warnings = Vec::new();
+    let labels = doc.labels();
+
+    let mut seen = HashSet::new();
+    let mut duplicates = HashSet::new();
+    for label in &labels {
+        if !seen.insert(label) && duplicates.insert(label) {
+            warnings.push(ValidationWarning::DuplicateLabel(label.clone()));
+        }
+    }
+
+    let mut references = CollectReferences::default();
+    references
+        .visit_document(doc)
+        .expect("Collecting references never fails");
+
+    let mut dangling = HashSet::new();
+    for reference in references.targets {
+        if !seen.contains(&reference) && dangling.insert(reference.clone()) {
+            warnings.push(ValidationWarning::DanglingRef(reference));
+        }
+    }
+
+    let mut bib_resources = doc
+        .preamble
+        .iter()
+        .filter_map(|element| match *element {
+            PreambleElement::AddBibResource(ref path) => Some(path),
+            _ => None,
+        })
+        .peekable();
+    if bib_resources.peek().is_none() {
+        return warnings;
+    }
+
+    let mut entries = HashSet::new();
+    for path in bib_resources {
+        match fs::read_to_string(path) {
+            Ok(contents) => entries.extend(bib_keys(&contents)),
+            Err(_) => warnings.push(ValidationWarning::MissingBibResource(path.clone())),
+        }
+    }
+
+    let mut dangling = HashSet::new();
+    for key in references.citations {
+        if !entries.contains(&key) && dangling.insert(key.clone()) {
+            warnings.push(ValidationWarning::DanglingCite(key));
+        }
+    }
+
+    warnings
+}
+
+/// Get the key of every entry in a `.bib` file (e.g. `knuth84` in
+/// `@book{knuth84, ...}`), ignoring `@string`, `@preamble`, and `@comment`.
+fn bib_keys(contents: &str) -> Vec<String> {
+    let mut keys = Vec::new();
+
+    for entry in contents.split('@').skip(1) {
+        let open = match entry.find(&['{', '('][..]) {
+            Some(open) => open,
+            None => continue,
+        };
+        let kind = entry[..open].trim().to_lowercase();
+        if kind.is_empty()
+            || !kind.chars().all(|c| c.is_ascii_alphabetic())
+            || kind == "string"
+            || kind == "preamble"
+            || kind == "comment"
+        {
+            continue;
+        }
+
+        let rest = &entry[open + 1..];
+        if let Some(comma) = rest.find(',') {
+            let key = rest[..comma].trim();
+            if !key.is_empty() {
+                keys.push(key.to_string());
+            }
+        }
+    }
+
+    keys
+}
+
+/// A potential problem found by `validate()`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ValidationWarning {
+    /// A `\ref` or `\pageref` to a label which is never defined.
+    DanglingRef(String),
+    /// A label which is defined more than once.
+    DuplicateLabel(String),
+    /// A `\cite` key which isn't in any of the document's `.bib` files.
+    DanglingCite(String),
+    /// A `.bib` file added to the preamble which couldn't be read.
+    MissingBibResource(String),
+}
+
+impl Display for ValidationWarning {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            ValidationWarning::DanglingRef(ref label) => {
+                write!(f, "Reference to undefined label \"{}\"", label)
+            }
+            ValidationWarning::DuplicateLabel(ref label) => {
+                write!(f, "The label \"{}\" is defined more than once", label)
+            }
+            ValidationWarning::DanglingCite(ref key) => {
+                write!(f, "Citation of undefined bibliography entry \"{}\"", key)
+            }
+            ValidationWarning::MissingBibResource(ref path) => {
+                write!(f, "Unable to read the bibliography \"{}\"", path)
+            }
+        }
+    }
+}
+
+/// A `Visitor` which collects the targets of every `\ref` and `\pageref`,
+/// and the keys of every `\cite`.
+#[derive(Default)]
+struct CollectReferences {
+    targets: Vec<String>,
+    citations: Vec<String>,
+}
+
+impl Visitor for CollectReferences {
+    fn visit_paragraph_element(&mut self, element: &ParagraphElement) -> Result<(), Error> {
+        match *element {
+            ParagraphElement::Ref(ref label) | ParagraphElement::Pageref(ref label) => {
+                self.targets.push(label.clone())
+            }
+            ParagraphElement::Cite { ref keys, .. } => self.citations.extend(keys.iter().cloned()),
+            _ => {}
+        }
+
+        if let Some(child) = element.children() {
+            self.visit_paragraph_element(child)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use {Align, DocumentClass, Equation, Paragraph, Section};
+
+    #[test]
+    fn valid_document_has_no_warnings() {
+        let mut section = Section::new("Intro");
+        section.label("sec:intro");
+        let mut para = Paragraph::new();
+        para.push("See section ")
+            .push(ParagraphElement::reference("sec:intro"));
+
+        let mut doc = Document::new(DocumentClass::Article);
+        doc.push(section).push(para);
+
+        assert!(validate(&doc).is_empty());
+    }
+
+    #[test]
+    fn dangling_reference() {
+        let mut para = Paragraph::new();
+        para.push("See ")
+            .push(ParagraphElement::bold(ParagraphElement::reference(
+                "eq:missing",
+            )))
+            .push(" and ")
+            .push(ParagraphElement::Pageref("eq:missing".to_string()));
+
+        let mut doc = Document::new(DocumentClass::Article);
+        doc.push(para);
+
+        assert_eq!(
+            validate(&doc),
+            vec![ValidationWarning::DanglingRef("eq:missing".to_string())]
+        );
+    }
+
+    #[test]
+    fn duplicate_label() {
+        let mut align = Align::new();
+        align
+            .push(Equation::with_label("eq:1", "a &= b"))
+            .push(Equation::with_label("eq:1", "c &= d"));
+
+        let mut doc = Document::new(DocumentClass::Article);
+        doc.push(align);
+
+        assert_eq!(
+            validate(&doc),
+            vec![ValidationWarning::DuplicateLabel("eq:1".to_string())]
+        );
+    }
+
+    #[test]
+    fn read_keys_from_a_bib_file() {
+        let src = r#"
+            @string{tex = "TeX"}
+            @book{knuth84,
+              title = {The {\TeX}book},
+              author = {Donald Knuth},
+              note = {knuth@example.com},
+            }
+            @Article( lamport94 , title = "LaTeX")
+        "#;
+
+        assert_eq!(bib_keys(src), vec!["knuth84", "lamport94"]);
+    }
+
+    #[test]
+    fn dangling_citation() {
+        let bib = env::temp_dir().join("latex-rs-dangling-citation.bib");
+        fs::write(&bib, "@book{knuth84, title = {The TeXbook}}").unwrap();
+
+        let mut para = Paragraph::new();
+        para.push(ParagraphElement::cite(&["knuth84", "missing"]));
+        let mut doc = Document::new(DocumentClass::Article);
+        doc.preamble.add_bib_resource(bib.to_str().unwrap());
+        doc.push(para);
+
+        let warnings = validate(&doc);
+        fs::remove_file(&bib).unwrap();
+
+        assert_eq!(
+            warnings,
+            vec![ValidationWarning::DanglingCite("missing".to_string())]
+        );
+    }
+
+    #[test]
+    fn unreadable_bib_resource() {
+        let mut para = Paragraph::new();
+        para.push(ParagraphElement::cite(&["knuth84"]));
+        let mut doc = Document::new(DocumentClass::Article);
+        doc.preamble.add_bib_resource("this/does/not/exist.bib");
+        doc.push(para);
+
+        assert_eq!(
+            validate(&doc),
+            vec![
+                ValidationWarning::MissingBibResource("this/does/not/exist.bib".to_string()),
+                ValidationWarning::DanglingCite("knuth84".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn citations_arent_checked_without_a_bib_resource() {
+        let mut para = Paragraph::new();
+        para.push(ParagraphElement::cite(&["knuth84"]));
+        let mut doc = Document::new(DocumentClass::Article);
+        doc.push(para);
+
+        assert!(validate(&doc).is_empty());
+    }
+}
@@ -1,16 +1,23 @@
 //! A trait which lets you walk your document's AST.
 
 mod printer;
+mod visitor_mut;
+mod word_count;
 
-pub use self::printer::{print, Printer};
+pub use self::printer::{print, print_to, Printer};
+pub use self::visitor_mut::VisitorMut;
+pub use self::word_count::{count, WordCount};
 
 use document::{Document, DocumentClass, Element, Preamble};
-use equations::{Align, Equation};
+use equations::{Align, Equation, SplitEquation};
 use failure::Error;
+use figure::{Figure, Image};
+use listing::{Listing, MintedListing};
 use lists::{Item, List};
 use paragraph::{Paragraph, ParagraphElement};
 use section::Section;
 use std::ops::Deref;
+use theorem::Theorem;
 
 /// A trait which uses the [Visitor Pattern] to recursively visit each node in
 /// a `Document`.
@@ -36,20 +43,53 @@ pub trait Visitor {
     /// `visit_*()` methods.
     ///
     /// > **Note:** You probably don't want to implement this one yourself. If
-    /// you forget to recursively visit each and every variant of `Element`
-    /// you may end up accidentally ignoring half your document!
+    /// > you forget to recursively visit each and every variant of `Element`
+    /// > you may end up accidentally ignoring half your document!
     fn visit_element(&mut self, elem: &Element) -> Result<(), Error> {
         match *elem {
             Element::Para(ref p) => self.visit_paragraph(p)?,
             Element::Section(ref s) => self.visit_section(s)?,
             Element::UserDefined(ref s) => self.visit_user_defined_line(s)?,
             Element::Align(ref equations) => self.visit_align(equations)?,
-
-            Element::Environment(ref name, ref lines) => {
-                self.visit_custom_environment(name, lines.iter().map(Deref::deref))?
-            }
+            Element::SplitEquation(ref eq) => self.visit_split_equation(eq)?,
+
+            Element::Environment(ref name, ref lines)
+            | Element::EnvironmentWith {
+                ref name,
+                ref lines,
+                ..
+            } => self.visit_custom_environment(name, lines.iter().map(Deref::deref))?,
             Element::List(ref list) => self.visit_list(list)?,
             Element::Input(ref s) => self.visit_input(s)?,
+            Element::Abstract(ref paragraphs) => {
+                for paragraph in paragraphs {
+                    self.visit_paragraph(paragraph)?;
+                }
+            }
+            Element::Figure(ref figure) => self.visit_figure(figure)?,
+            Element::Theorem(ref theorem) => self.visit_theorem(theorem)?,
+            Element::Listing(ref listing) => self.visit_listing(listing)?,
+            Element::MintedListing(ref listing) => self.visit_minted_listing(listing)?,
+            Element::MultiCols {
+                columns,
+                body: ref elements,
+            } => self.visit_multicols(columns, elements)?,
+            Element::TikzPicture {
+                ref options,
+                ref body,
+            } => self.visit_tikz_picture(options.as_ref().map(Deref::deref), body)?,
+            Element::PrintBibliography => self.visit_print_bibliography()?,
+            Element::Center(ref elements)
+            | Element::Minipage {
+                body: ref elements, ..
+            }
+            | Element::Block {
+                body: ref elements, ..
+            } => {
+                for element in elements {
+                    self.visit_element(element)?;
+                }
+            }
 
             _ => {}
         }
@@ -62,8 +102,13 @@ pub trait Visitor {
         Ok(())
     }
 
-    /// Visit an element in a `Paragraph` (e.g. `Italic`, `InlineCode`).
+    /// Visit an element in a `Paragraph` (e.g. `Italic`, `InlineCode`), then
+    /// recursively visit the element it wraps (if any).
     fn visit_paragraph_element(&mut self, element: &ParagraphElement) -> Result<(), Error> {
+        if let Some(child) = element.children() {
+            self.visit_paragraph_element(child)?;
+        }
+
         Ok(())
     }
 
@@ -95,6 +140,15 @@ pub trait Visitor {
         Ok(())
     }
 
+    /// Visit a `Theorem` and then recursively visit each of its `Element`s.
+    fn visit_theorem(&mut self, theorem: &Theorem) -> Result<(), Error> {
+        for elem in theorem.iter() {
+            self.visit_element(elem)?;
+        }
+
+        Ok(())
+    }
+
     /// Visit an `Align` block and then recursively visit each equation in the
     /// block.
     fn visit_align(&mut self, align: &Align) -> Result<(), Error> {
@@ -110,6 +164,11 @@ pub trait Visitor {
         Ok(())
     }
 
+    /// Visit a `SplitEquation`.
+    fn visit_split_equation(&mut self, equation: &SplitEquation) -> Result<(), Error> {
+        Ok(())
+    }
+
     /// Visit a `List` and all of its items.
     fn visit_list(&mut self, list: &List) -> Result<(), Error> {
         for item in list.iter() {
@@ -119,8 +178,51 @@ pub trait Visitor {
         Ok(())
     }
 
-    /// Visit a single list item.
+    /// Visit a single list item, recursively visiting any nested list.
     fn visit_list_item(&mut self, item: &Item) -> Result<(), Error> {
+        if let Item::Sublist(ref list) = *item {
+            self.visit_list(list)?;
+        }
+
+        Ok(())
+    }
+
+    /// Visit a `Figure` and the image inside it.
+    fn visit_figure(&mut self, figure: &Figure) -> Result<(), Error> {
+        self.visit_image(&figure.image)
+    }
+
+    /// Visit a single `Image`.
+    fn visit_image(&mut self, image: &Image) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Visit a `Listing` of source code.
+    fn visit_listing(&mut self, listing: &Listing) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Visit a `MintedListing` of source code.
+    fn visit_minted_listing(&mut self, listing: &MintedListing) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Visit a `multicols` environment, then each element in its body.
+    fn visit_multicols(&mut self, columns: usize, body: &[Element]) -> Result<(), Error> {
+        for element in body {
+            self.visit_element(element)?;
+        }
+
+        Ok(())
+    }
+
+    /// Visit a `tikzpicture` environment and its raw TeX.
+    fn visit_tikz_picture(&mut self, options: Option<&str>, body: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Visit a `\printbibliography` command.
+    fn visit_print_bibliography(&mut self) -> Result<(), Error> {
         Ok(())
     }
 
@@ -1,29 +1,37 @@
-use std::io::Write;
+use std::io::{self, Write};
 
 use super::Visitor;
 use document::{Document, DocumentClass, Element, Preamble, PreambleElement};
-use equations::{Align, Equation};
-use failure::Error;
+use equations::{Align, AlignItem, Equation, MathEnvironment, SplitEquation};
+use failure::{err_msg, Error};
+use figure::{Figure, Image};
+use listing::{Listing, MintedListing};
 use lists::{Item, List};
 use paragraph::{Paragraph, ParagraphElement};
 use section::Section;
+use theorem::Theorem;
 
 /// Print a document to a string.
 pub fn print(doc: &Document) -> Result<String, Error> {
     let mut buffer = Vec::new();
-    {
-        let mut printer = Printer::new(&mut buffer);
-        printer.visit_document(doc)?;
-    }
+    print_to(doc, &mut buffer)?;
 
     let rendered = String::from_utf8(buffer)?;
     Ok(rendered)
 }
 
+/// Print a document directly to a `Writer` (e.g. a `File`), without building
+/// the entire rendered document in memory first.
+pub fn print_to<W: Write>(doc: &Document, writer: W) -> Result<(), Error> {
+    let mut printer = Printer::new(writer);
+    printer.visit_document(doc)
+}
+
 /// The type which uses the `Visitor` pattern to visit each node in a document
 /// and write its `tex` representation to a `Writer`.
 pub struct Printer<W> {
-    writer: W,
+    writer: Indenter<W>,
+    escape_special_chars: bool,
 }
 
 impl<W> Printer<W>
@@ -32,8 +40,430 @@ where
 {
     /// Create a new `Printer` which will write to the provided `Writer`.
     pub fn new(writer: W) -> Printer<W> {
-        Printer { writer }
+        Printer {
+            writer: Indenter::new(writer),
+            escape_special_chars: false,
+        }
+    }
+
+    /// Set whether LaTeX special characters (`&`, `%`, `$`, `#`, `_`, `{`,
+    /// `}`, `~`, `^`, and `\`) in plain paragraph text should be escaped.
+    ///
+    /// This is disabled by default, meaning plain text is written as-is.
+    pub fn escape_special_chars(&mut self, enabled: bool) -> &mut Self {
+        self.escape_special_chars = enabled;
+        self
+    }
+
+    /// Indent the contents of each environment (lists, equations, figures,
+    /// etc) by this many spaces per level of nesting.
+    ///
+    /// The body of the `document` environment, the contents of verbatim
+    /// environments like `lstlisting`, and raw TeX (the lines of an
+    /// `Element::Environment` or the body of an `Element::TikzPicture`) are
+    /// never indented. This defaults to `0`,
+    /// meaning no indentation is added.
+    ///
+    /// Nested lists are always indented relative to their parent item (by 2
+    /// spaces when this is `0`) so the structure stays readable.
+    pub fn indent_environments(&mut self, spaces: usize) -> &mut Self {
+        self.writer.width = spaces;
+        self
+    }
+
+    /// Run the provided closure with everything it writes indented one
+    /// level deeper.
+    fn indented<F>(&mut self, body: F) -> Result<(), Error>
+    where
+        F: FnOnce(&mut Self) -> Result<(), Error>,
+    {
+        let width = self.writer.width;
+        self.indented_by(width, body)
+    }
+
+    /// Run the provided closure with everything it writes indented by an
+    /// extra `spaces`.
+    fn indented_by<F>(&mut self, spaces: usize, body: F) -> Result<(), Error>
+    where
+        F: FnOnce(&mut Self) -> Result<(), Error>,
+    {
+        self.writer.indent += spaces;
+        let result = body(self);
+        self.writer.indent -= spaces;
+        result
+    }
+
+    /// Write the elements making up an environment's body, indented and
+    /// separated by blank lines.
+    fn write_body<'a, I>(&mut self, elements: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = &'a Element>,
+    {
+        self.indented(|p| {
+            for (i, element) in elements.into_iter().enumerate() {
+                if i > 0 {
+                    // LaTeX needs an empty line between paragraphs
+                    writeln!(p.writer)?;
+                }
+                p.visit_element(element)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Write some text which must be reproduced exactly, without any
+    /// indentation.
+    fn write_verbatim(&mut self, text: &str) -> Result<(), Error> {
+        let indent = self.writer.indent;
+        self.writer.indent = 0;
+        let result = writeln!(self.writer, "{}", text);
+        self.writer.indent = indent;
+        result?;
+
+        Ok(())
+    }
+
+    /// Write a generic environment, its arguments, and its lines. The lines
+    /// are raw TeX (which may be verbatim), so they are never indented.
+    fn write_environment(
+        &mut self,
+        name: &str,
+        options: &[String],
+        args: &[String],
+        lines: &[String],
+    ) -> Result<(), Error> {
+        write!(self.writer, r"\begin{{{}}}", name)?;
+        if !options.is_empty() {
+            write!(self.writer, "[{}]", options.join(","))?;
+        }
+        for arg in args {
+            write!(self.writer, "{{{}}}", arg)?;
+        }
+        writeln!(self.writer)?;
+
+        for line in lines {
+            self.write_verbatim(line)?;
+        }
+        writeln!(self.writer, r"\end{{{}}}", name)?;
+
+        Ok(())
+    }
+
+    /// Write the `align`-like environment containing an `Align`'s equations.
+    fn write_align_environment(&mut self, align: &Align, env: &str) -> Result<(), Error> {
+        writeln!(self.writer, r"\begin{{{}}}", env)?;
+
+        // starred environments are never numbered, so \nonumber is redundant
+        let starred = !align.is_numbered();
+        // any trailing \intertext doesn't count as the last row
+        let last_equation = align
+            .items()
+            .rposition(|item| matches!(*item, AlignItem::Equation(_)));
+
+        self.indented(|p| {
+            for (i, item) in align.items().enumerate() {
+                match *item {
+                    AlignItem::Equation(ref eq) => {
+                        let line_break =
+                            align.uses_trailing_backslash() || Some(i) != last_equation;
+                        p.write_equation(eq, starred, line_break)?;
+                    }
+                    AlignItem::InterText(ref text) => {
+                        writeln!(p.writer, r"\intertext{{{}}}", text)?
+                    }
+                }
+            }
+            Ok(())
+        })?;
+
+        writeln!(self.writer, r"\end{{{}}}", env)?;
+
+        Ok(())
+    }
+
+    /// Write an `equation` or `multline` environment. These hold a single
+    /// (possibly multi-line) equation with at most one number, so only
+    /// `multline` may contain several lines and only one label or tag is
+    /// allowed.
+    fn write_single_equation(&mut self, align: &Align, env: &str) -> Result<(), Error> {
+        let environment = align.environment();
+        let name = environment.environment_name();
+        let lines: Vec<&Equation> = align.iter().collect();
+
+        if lines.len() != align.items().len() {
+            return Err(err_msg(format!(
+                "\\intertext can't be used in a {} environment",
+                name
+            )));
+        }
+        if environment == MathEnvironment::Equation && lines.len() > 1 {
+            return Err(err_msg(
+                "An equation environment can only contain one equation, use multline or align instead",
+            ));
+        }
+
+        let labels: Vec<&str> = lines.iter().filter_map(|eq| eq.get_label()).collect();
+        let tags: Vec<&str> = lines.iter().filter_map(|eq| eq.get_tag()).collect();
+        if labels.len() > 1 || tags.len() > 1 {
+            return Err(err_msg(format!(
+                "A {} environment can only have one label and one tag",
+                name
+            )));
+        }
+        let nonumber = align.is_numbered() && lines.iter().all(|eq| !eq.is_numbered());
+
+        writeln!(self.writer, r"\begin{{{}}}", env)?;
+        self.indented(|p| {
+            let last = lines.len().saturating_sub(1);
+            for (i, eq) in lines.iter().enumerate() {
+                write!(p.writer, "{}", eq.get_text())?;
+
+                if i < last {
+                    writeln!(p.writer, r" \\")?;
+                    continue;
+                }
+                if let Some(label) = labels.first() {
+                    write!(p.writer, r" \label{{{}}}", label)?;
+                }
+                if let Some(tag) = tags.first() {
+                    write!(p.writer, r" \tag{{{}}}", tag)?;
+                } else if nonumber {
+                    write!(p.writer, r" \nonumber")?;
+                }
+                writeln!(p.writer)?;
+            }
+            Ok(())
+        })?;
+        writeln!(self.writer, r"\end{{{}}}", env)?;
+
+        Ok(())
+    }
+
+    /// Write a single row of an `align`-like environment. Unnumbered rows
+    /// only need a `\nonumber` when the environment isn't `starred`.
+    fn write_equation(
+        &mut self,
+        equation: &Equation,
+        starred: bool,
+        line_break: bool,
+    ) -> Result<(), Error> {
+        write!(self.writer, r"{}", equation.get_text())?;
+
+        if let Some(ref label) = equation.get_label() {
+            write!(self.writer, r" \label{{{}}}", label)?;
+        }
+        if let Some(tag) = equation.get_tag() {
+            write!(self.writer, r" \tag{{{}}}", tag)?;
+        } else if !equation.is_numbered() && !starred {
+            write!(self.writer, r" \nonumber")?;
+        }
+
+        if line_break {
+            writeln!(self.writer, r" \\")?;
+        } else {
+            writeln!(self.writer)?;
+        }
+        Ok(())
+    }
+
+    /// Write a single element from the document's preamble.
+    fn write_preamble_element(&mut self, item: &PreambleElement) -> Result<(), Error> {
+        match item {
+            PreambleElement::UsePackage {
+                package: pkg,
+                argument: None,
+            } => writeln!(self.writer, r"\usepackage{{{}}}", pkg)?,
+            PreambleElement::UsePackage {
+                package: pkg,
+                argument: Some(arg),
+            } => writeln!(self.writer, r"\usepackage[{}]{{{}}}", arg, pkg)?,
+            PreambleElement::NewCommand {
+                name,
+                args_num,
+                default_arg,
+                definition,
+            } => {
+                self.write_command_signature("newcommand", name, *args_num, default_arg)?;
+                writeln!(self.writer, r"{{")?;
+                writeln!(self.writer, "{}", definition)?;
+                writeln!(self.writer, r"}}")?;
+            }
+            PreambleElement::RenewCommand {
+                name,
+                args_num,
+                default_arg,
+                definition,
+            } => {
+                self.write_command_signature("renewcommand", name, *args_num, default_arg)?;
+                writeln!(self.writer, r"{{{}}}", definition)?;
+            }
+            PreambleElement::NewEnvironment {
+                name,
+                args_num,
+                begin_def,
+                end_def,
+            } => {
+                write!(self.writer, r"\newenvironment{{{}}}", name)?;
+                if let Some(num) = args_num {
+                    write!(self.writer, r"[{}]", num)?;
+                }
+                writeln!(self.writer, r"{{{}}}{{{}}}", begin_def, end_def)?;
+            }
+            PreambleElement::NewTheorem {
+                env,
+                display,
+                counter,
+            } => {
+                write!(self.writer, r"\newtheorem{{{}}}{{{}}}", env, display)?;
+                if let Some(counter) = counter {
+                    write!(self.writer, "[{}]", counter)?;
+                }
+                writeln!(self.writer)?;
+            }
+            PreambleElement::AddBibResource(path) => {
+                writeln!(self.writer, r"\addbibresource{{{}}}", path)?
+            }
+            PreambleElement::HyperSetup(options) => {
+                let options: Vec<String> = options
+                    .iter()
+                    .map(|(key, value)| format!("{}={{{}}}", key, value))
+                    .collect();
+                writeln!(self.writer, r"\hypersetup{{{}}}", options.join(","))?;
+            }
+            PreambleElement::GraphicsPath(paths) => {
+                write!(self.writer, r"\graphicspath{{")?;
+                for path in paths {
+                    write!(self.writer, "{{{}}}", path)?;
+                }
+                writeln!(self.writer, "}}")?;
+            }
+            PreambleElement::IncludeOnly(files) => {
+                writeln!(self.writer, r"\includeonly{{{}}}", files.join(","))?
+            }
+            PreambleElement::NewCounter { name, within } => {
+                write!(self.writer, r"\newcounter{{{}}}", name)?;
+                if let Some(within) = within {
+                    write!(self.writer, "[{}]", within)?;
+                }
+                writeln!(self.writer)?;
+            }
+            PreambleElement::SetLength { register, value } => {
+                writeln!(self.writer, r"\setlength{{\{}}}{{{}}}", register, value)?
+            }
+            PreambleElement::UserDefined(s) => writeln!(self.writer, r"{}", s)?,
+        }
+
+        Ok(())
+    }
+
+    /// Write the `\newcommand{\name}[args_num][default_arg]` part of a
+    /// command definition.
+    fn write_command_signature(
+        &mut self,
+        command: &str,
+        name: &str,
+        args_num: Option<usize>,
+        default_arg: &Option<String>,
+    ) -> Result<(), Error> {
+        write!(self.writer, r"\{}{{\{}}}", command, name)?;
+        if let Some(num) = args_num {
+            write!(self.writer, r"[{}]", num)?;
+        }
+        if let Some(arg) = default_arg {
+            write!(self.writer, r"[{}]", arg)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Where a `PreambleElement` is placed when the preamble is sorted on
+/// render.
+fn render_order(item: &PreambleElement) -> u8 {
+    match *item {
+        PreambleElement::UsePackage { .. } | PreambleElement::AddBibResource(_) => 0,
+        PreambleElement::UserDefined(_) => 2,
+        _ => 1,
+    }
+}
+
+/// A `Writer` adapter which inserts indentation at the start of each
+/// non-empty line.
+struct Indenter<W> {
+    inner: W,
+    /// The number of spaces added for each level of nesting.
+    width: usize,
+    /// The number of spaces currently inserted at the start of a line.
+    indent: usize,
+    at_line_start: bool,
+}
+
+impl<W> Indenter<W> {
+    fn new(inner: W) -> Indenter<W> {
+        Indenter {
+            inner,
+            width: 0,
+            indent: 0,
+            at_line_start: true,
+        }
+    }
+}
+
+impl<W> Write for Indenter<W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for line in buf.split_inclusive(|&b| b == b'\n') {
+            if self.at_line_start && line != b"\n" {
+                let indent = vec![b' '; self.indent];
+                self.inner.write_all(&indent)?;
+            }
+            self.inner.write_all(line)?;
+            self.at_line_start = line.ends_with(b"\n");
+        }
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// The characters which may be used to delimit a `\verb`, in order of
+/// preference.
+const VERB_DELIMITERS: &[char] = &['|', '!', '+', '@', '/', ':', ';', '=', '-', '"'];
+
+/// The English suffix for an ordinal number (e.g. `st` for 21).
+fn ordinal_suffix(n: u64) -> &'static str {
+    match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    }
+}
+
+/// Escape any characters which have a special meaning in LaTeX.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '~' => escaped.push_str(r"\textasciitilde{}"),
+            '^' => escaped.push_str(r"\textasciicircum{}"),
+            '\\' => escaped.push_str(r"\textbackslash{}"),
+            _ => escaped.push(c),
+        }
     }
+
+    escaped
 }
 
 impl<W> Visitor for Printer<W>
@@ -50,7 +480,11 @@ where
             }
             // write a full document
             _ => {
-                writeln!(self.writer, r"\documentclass{{{}}}", doc.class)?;
+                write!(self.writer, r"\documentclass")?;
+                if !doc.class_options.is_empty() {
+                    write!(self.writer, "[{}]", doc.class_options.join(","))?;
+                }
+                writeln!(self.writer, "{{{}}}", doc.class)?;
 
                 self.visit_preamble(&doc.preamble)?;
 
@@ -77,8 +511,15 @@ where
 
     fn visit_paragraph_element(&mut self, element: &ParagraphElement) -> Result<(), Error> {
         match *element {
-            ParagraphElement::Plain(ref s) => write!(self.writer, "{}", s)?,
+            ParagraphElement::Plain(ref s) => {
+                if self.escape_special_chars {
+                    write!(self.writer, "{}", escape(s))?;
+                } else {
+                    write!(self.writer, "{}", s)?;
+                }
+            }
             ParagraphElement::InlineMath(ref s) => write!(self.writer, "${}$", s)?,
+            ParagraphElement::DisplayMath(ref s) => write!(self.writer, r"\[{}\]", s)?,
             ParagraphElement::Bold(ref e) => {
                 write!(self.writer, r"\textbf{{")?;
                 self.visit_paragraph_element(e)?;
@@ -89,44 +530,111 @@ where
                 self.visit_paragraph_element(e)?;
                 write!(self.writer, "}}")?;
             }
+            ParagraphElement::Underline(ref e) => {
+                write!(self.writer, r"\underline{{")?;
+                self.visit_paragraph_element(e)?;
+                write!(self.writer, "}}")?;
+            }
+            ParagraphElement::Strikeout(ref e) => {
+                write!(self.writer, r"\sout{{")?;
+                self.visit_paragraph_element(e)?;
+                write!(self.writer, "}}")?;
+            }
+            ParagraphElement::Superscript(ref e) => {
+                write!(self.writer, r"\textsuperscript{{")?;
+                self.visit_paragraph_element(e)?;
+                write!(self.writer, "}}")?;
+            }
+            ParagraphElement::Subscript(ref e) => {
+                write!(self.writer, r"\textsubscript{{")?;
+                self.visit_paragraph_element(e)?;
+                write!(self.writer, "}}")?;
+            }
+            ParagraphElement::SmallCaps(ref e) => {
+                write!(self.writer, r"\textsc{{")?;
+                self.visit_paragraph_element(e)?;
+                write!(self.writer, "}}")?;
+            }
+            ParagraphElement::Emph(ref e) => {
+                write!(self.writer, r"\emph{{")?;
+                self.visit_paragraph_element(e)?;
+                write!(self.writer, "}}")?;
+            }
+            ParagraphElement::Monospace(ref e) => {
+                write!(self.writer, r"\texttt{{")?;
+                self.visit_paragraph_element(e)?;
+                write!(self.writer, "}}")?;
+            }
+            ParagraphElement::Link { ref url, ref text } => {
+                write!(self.writer, r"\href{{{}}}{{", url)?;
+                self.visit_paragraph_element(text)?;
+                write!(self.writer, "}}")?;
+            }
+            ParagraphElement::Url(ref url) => write!(self.writer, r"\url{{{}}}", url)?,
+            ParagraphElement::Colored {
+                ref color,
+                ref inner,
+            } => {
+                write!(self.writer, r"\textcolor{{{}}}{{", color)?;
+                self.visit_paragraph_element(inner)?;
+                write!(self.writer, "}}")?;
+            }
+            ParagraphElement::Verb(ref s) => {
+                let delim = VERB_DELIMITERS
+                    .iter()
+                    .find(|&&c| !s.contains(c))
+                    .ok_or_else(|| err_msg(format!("No suitable \\verb delimiter for {:?}", s)))?;
+                write!(self.writer, r"\verb{}{}{}", delim, s, delim)?;
+            }
+            ParagraphElement::HSpace(ref space) => write!(self.writer, r"\hspace{{{}}}", space)?,
+            ParagraphElement::LineBreak => write!(self.writer, r"\\")?,
+            ParagraphElement::NonBreakingSpace => write!(self.writer, "~")?,
+            ParagraphElement::ThinSpace => write!(self.writer, r"\,")?,
+            ParagraphElement::Ordinal(n) => write!(
+                self.writer,
+                r"{}\textsuperscript{{{}}}",
+                n,
+                ordinal_suffix(n)
+            )?,
+            ParagraphElement::LineBreakWith(ref space) => write!(self.writer, r"\\[{}]", space)?,
+            ParagraphElement::Ref(ref label) => write!(self.writer, r"\ref{{{}}}", label)?,
+            ParagraphElement::Pageref(ref label) => write!(self.writer, r"\pageref{{{}}}", label)?,
+            ParagraphElement::Cite {
+                ref keys,
+                ref pre_note,
+                ref post_note,
+            } => {
+                write!(self.writer, r"\cite")?;
+                match (pre_note, post_note) {
+                    (Some(pre), Some(post)) => write!(self.writer, "[{}][{}]", pre, post)?,
+                    (Some(pre), None) => write!(self.writer, "[{}][]", pre)?,
+                    (None, Some(post)) => write!(self.writer, "[{}]", post)?,
+                    (None, None) => {}
+                }
+                write!(self.writer, "{{{}}}", keys.join(","))?;
+            }
         }
 
         Ok(())
     }
 
     fn visit_preamble(&mut self, preamble: &Preamble) -> Result<(), Error> {
-        for item in preamble.iter() {
-            match item {
-                PreambleElement::UsePackage {
-                    package: pkg,
-                    argument: None,
-                } => writeln!(self.writer, r"\usepackage{{{}}}", pkg)?,
-                PreambleElement::UsePackage {
-                    package: pkg,
-                    argument: Some(arg),
-                } => writeln!(self.writer, r"\usepackage[{}]{{{}}}", arg, pkg)?,
-                PreambleElement::NewCommand {
-                    name,
-                    args_num,
-                    default_arg,
-                    definition
-                } => {
-                    write!(self.writer, r"\newcommand{{\{}}}", name)?;
-                    if let Some(num) = args_num {
-                        write!(self.writer, r"[{}]", num)?;
-                    }
-                    if let Some(arg) = default_arg {
-                        write!(self.writer, r"[{}]", arg)?;
-                    }
-                    writeln!(self.writer, r"{{")?;
-                    writeln!(self.writer, "{}", definition)?;
-                    writeln!(self.writer, r"}}")?;
-                },
-                PreambleElement::UserDefined(s) => writeln!(self.writer, r"{}", s)?,
+        if preamble.sorts_on_render() {
+            let mut items: Vec<&PreambleElement> = preamble.iter().collect();
+            // sort_by_key() is stable, so each group keeps its original order
+            items.sort_by_key(|item| render_order(item));
+            for item in items {
+                self.write_preamble_element(item)?;
+            }
+        } else {
+            for item in preamble.iter() {
+                self.write_preamble_element(item)?;
             }
         }
 
-        if !preamble.is_empty() && (preamble.title.is_some() || preamble.author.is_some()) {
+        if !preamble.is_empty()
+            && (preamble.title.is_some() || preamble.author.is_some() || preamble.date.is_some())
+        {
             writeln!(self.writer)?;
         }
 
@@ -136,18 +644,26 @@ where
         if let Some(ref author) = preamble.author {
             writeln!(self.writer, r"\author{{{}}}", author)?;
         }
+        if let Some(ref date) = preamble.date {
+            writeln!(self.writer, r"\date{{{}}}", date)?;
+        }
 
         Ok(())
     }
 
     fn visit_list(&mut self, list: &List) -> Result<(), Error> {
         let env = list.kind.environment_name();
-
         writeln!(self.writer, r"\begin{{{}}}", env)?;
 
-        for item in list.iter() {
-            self.visit_list_item(item)?;
-        }
+        self.indented(|p| {
+            // LaTeX won't start a list without an \item, so a leading sublist
+            // gets an empty one
+            if let Some(&Item::Sublist(_)) = list.iter().next() {
+                writeln!(p.writer, r"\item")?;
+            }
+
+            list.iter().try_for_each(|item| p.visit_list_item(item))
+        })?;
 
         writeln!(self.writer, r"\end{{{}}}", env)?;
 
@@ -155,7 +671,24 @@ where
     }
 
     fn visit_list_item(&mut self, item: &Item) -> Result<(), Error> {
-        writeln!(self.writer, r"\item {}", item.0)?;
+        match *item {
+            Item::Text(ref text) => {
+                writeln!(self.writer, r"\item {}", text)?;
+            }
+            Item::Labeled {
+                ref label,
+                ref text,
+            } => {
+                writeln!(self.writer, r"\item[{}] {}", label, text)?;
+            }
+            Item::Sublist(ref list) => {
+                // items are already indented when environments are, otherwise
+                // nested lists still need to stand out from their parent
+                let spaces = if self.writer.width > 0 { 0 } else { 2 };
+                self.indented_by(spaces, |p| p.visit_list(list))?;
+            }
+        }
+
         Ok(())
     }
 
@@ -164,20 +697,120 @@ where
             Element::Para(ref p) => self.visit_paragraph(p)?,
             Element::Section(ref s) => self.visit_section(s)?,
             Element::TableOfContents => writeln!(self.writer, r"\tableofcontents")?,
+            Element::ListOfFigures => writeln!(self.writer, r"\listoffigures")?,
+            Element::ListOfTables => writeln!(self.writer, r"\listoftables")?,
             Element::TitlePage => writeln!(self.writer, r"\maketitle")?,
             Element::ClearPage => writeln!(self.writer, r"\clearpage")?,
+            Element::Appendix => writeln!(self.writer, r"\appendix")?,
+            Element::FrontMatter => writeln!(self.writer, r"\frontmatter")?,
+            Element::MainMatter => writeln!(self.writer, r"\mainmatter")?,
+            Element::BackMatter => writeln!(self.writer, r"\backmatter")?,
             Element::UserDefined(ref s) => writeln!(self.writer, "{}", s)?,
             Element::Align(ref equations) => self.visit_align(equations)?,
+            Element::SplitEquation(ref eq) => self.visit_split_equation(eq)?,
 
             Element::Environment(ref name, ref lines) => {
-                writeln!(self.writer, r"\begin{{{}}}", name)?;
-                for line in lines {
-                    writeln!(self.writer, "{}", line)?;
-                }
-                writeln!(self.writer, r"\end{{{}}}", name)?;
+                self.write_environment(name, &[], &[], lines)?
             }
+            Element::EnvironmentWith {
+                ref name,
+                ref options,
+                ref args,
+                ref lines,
+            } => self.write_environment(name, options, args, lines)?,
             Element::List(ref list) => self.visit_list(list)?,
             Element::Input(ref s) => writeln!(self.writer, "\\input{{{}}}", s)?,
+            Element::Abstract(ref paragraphs) => {
+                writeln!(self.writer, r"\begin{{abstract}}")?;
+                self.indented(|p| {
+                    for (i, paragraph) in paragraphs.iter().enumerate() {
+                        if i > 0 {
+                            // paragraphs need an empty line between them
+                            writeln!(p.writer)?;
+                        }
+                        p.visit_paragraph(paragraph)?;
+                    }
+                    Ok(())
+                })?;
+                writeln!(self.writer, r"\end{{abstract}}")?;
+            }
+            Element::Figure(ref figure) => self.visit_figure(figure)?,
+            Element::Theorem(ref theorem) => self.visit_theorem(theorem)?,
+            Element::Bibliography {
+                ref style,
+                ref sources,
+            } => {
+                writeln!(self.writer, r"\bibliographystyle{{{}}}", style)?;
+                writeln!(self.writer, r"\bibliography{{{}}}", sources)?;
+            }
+            Element::PrintBibliography => writeln!(self.writer, r"\printbibliography")?,
+            Element::Listing(ref listing) => self.visit_listing(listing)?,
+            Element::MintedListing(ref listing) => self.visit_minted_listing(listing)?,
+            Element::VSpace(ref space) => writeln!(self.writer, r"\vspace{{{}}}", space)?,
+            Element::HorizontalRule => {
+                writeln!(self.writer, r"\par\noindent\rule{{\textwidth}}{{0.4pt}}")?
+            }
+            Element::Rule {
+                ref width,
+                ref thickness,
+            } => writeln!(
+                self.writer,
+                r"\par\noindent\rule{{{}}}{{{}}}",
+                width, thickness
+            )?,
+            Element::Center(ref elements) => {
+                writeln!(self.writer, r"\begin{{center}}")?;
+                self.write_body(elements)?;
+                writeln!(self.writer, r"\end{{center}}")?;
+            }
+            Element::MultiCols { columns, ref body } => {
+                writeln!(self.writer, r"\begin{{multicols}}{{{}}}", columns)?;
+                self.write_body(body)?;
+                writeln!(self.writer, r"\end{{multicols}}")?;
+            }
+            Element::SetCounter { ref name, value } => {
+                writeln!(self.writer, r"\setcounter{{{}}}{{{}}}", name, value)?
+            }
+            Element::Comment(ref comment) => {
+                // str::lines() yields nothing for an empty string
+                if comment.is_empty() {
+                    writeln!(self.writer, "%")?;
+                }
+                for line in comment.lines() {
+                    if line.is_empty() {
+                        writeln!(self.writer, "%")?;
+                    } else {
+                        writeln!(self.writer, "% {}", line)?;
+                    }
+                }
+            }
+            Element::Minipage {
+                ref width,
+                ref body,
+            } => {
+                writeln!(self.writer, r"\begin{{minipage}}{{{}}}", width)?;
+                self.write_body(body)?;
+                writeln!(self.writer, r"\end{{minipage}}")?;
+            }
+            Element::TikzPicture {
+                ref options,
+                ref body,
+            } => {
+                write!(self.writer, r"\begin{{tikzpicture}}")?;
+                if let Some(ref options) = *options {
+                    write!(self.writer, "[{}]", options)?;
+                }
+                writeln!(self.writer)?;
+                for line in body.lines() {
+                    self.write_verbatim(line)?;
+                }
+                writeln!(self.writer, r"\end{{tikzpicture}}")?;
+            }
+            Element::Block { ref name, ref body } => {
+                writeln!(self.writer, r"\begin{{{}}}", name)?;
+                self.write_body(body)?;
+                writeln!(self.writer, r"\end{{{}}}", name)?;
+            }
 
             Element::_Other => unreachable!(),
         }
@@ -185,8 +818,107 @@ where
         Ok(())
     }
 
+    fn visit_theorem(&mut self, theorem: &Theorem) -> Result<(), Error> {
+        write!(self.writer, r"\begin{{{}}}", theorem.env)?;
+        if let Some(ref title) = theorem.title {
+            write!(self.writer, "[{}]", title)?;
+        }
+        writeln!(self.writer)?;
+
+        self.write_body(theorem.iter())?;
+
+        writeln!(self.writer, r"\end{{{}}}", theorem.env)?;
+
+        Ok(())
+    }
+
+    fn visit_figure(&mut self, figure: &Figure) -> Result<(), Error> {
+        write!(self.writer, r"\begin{{figure}}")?;
+        let placement = figure.placement.unwrap_or_default().to_string();
+        if !placement.is_empty() {
+            write!(self.writer, "[{}]", placement)?;
+        }
+        writeln!(self.writer)?;
+
+        self.indented(|p| {
+            writeln!(p.writer, r"\centering")?;
+
+            p.visit_image(&figure.image)?;
+
+            if let Some(ref caption) = figure.caption {
+                writeln!(p.writer, r"\caption{{{}}}", caption)?;
+            }
+            if let Some(ref label) = figure.label {
+                writeln!(p.writer, r"\label{{{}}}", label)?;
+            }
+            Ok(())
+        })?;
+
+        writeln!(self.writer, r"\end{{figure}}")?;
+
+        Ok(())
+    }
+
+    fn visit_image(&mut self, image: &Image) -> Result<(), Error> {
+        write!(self.writer, r"\includegraphics")?;
+
+        let options = image.options();
+        if !options.is_empty() {
+            write!(self.writer, "[{}]", options.join(","))?;
+        }
+
+        writeln!(self.writer, "{{{}}}", image.path)?;
+
+        Ok(())
+    }
+
+    fn visit_listing(&mut self, listing: &Listing) -> Result<(), Error> {
+        write!(self.writer, r"\begin{{lstlisting}}")?;
+
+        let options = listing.options();
+        if !options.is_empty() {
+            write!(self.writer, "[{}]", options.join(","))?;
+        }
+        writeln!(self.writer)?;
+
+        self.write_verbatim(&listing.code)?;
+        writeln!(self.writer, r"\end{{lstlisting}}")?;
+
+        Ok(())
+    }
+
+    fn visit_minted_listing(&mut self, listing: &MintedListing) -> Result<(), Error> {
+        write!(self.writer, r"\begin{{minted}}")?;
+
+        if !listing.options.is_empty() {
+            let options: Vec<String> = listing
+                .options
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, value))
+                .collect();
+            write!(self.writer, "[{}]", options.join(","))?;
+        }
+        writeln!(self.writer, "{{{}}}", listing.language)?;
+
+        self.write_verbatim(&listing.code)?;
+        writeln!(self.writer, r"\end{{minted}}")?;
+
+        Ok(())
+    }
+
     fn visit_section(&mut self, section: &Section) -> Result<(), Error> {
-        writeln!(self.writer, r"\section{{{}}}", section.name)?;
+        let star = if section.is_numbered() { "" } else { "*" };
+        write!(
+            self.writer,
+            r"\{}{}{{{}}}",
+            section.level.command_name(),
+            star,
+            section.name
+        )?;
+        if let Some(label) = section.get_label() {
+            write!(self.writer, r"\label{{{}}}", label)?;
+        }
+        writeln!(self.writer)?;
 
         if !section.is_empty() {
             // Make sure there's space between the \section{...} and the next line
@@ -204,28 +936,64 @@ where
     }
 
     fn visit_equation(&mut self, equation: &Equation) -> Result<(), Error> {
-        write!(self.writer, r"{}", equation.get_text())?;
+        self.write_equation(equation, false, true)
+    }
 
-        if let Some(ref label) = equation.get_label() {
-            write!(self.writer, r" \label{{{}}}", label)?;
-        }
-        if !equation.is_numbered() {
-            write!(self.writer, r" \nonumber")?;
-        }
+    fn visit_split_equation(&mut self, equation: &SplitEquation) -> Result<(), Error> {
+        writeln!(self.writer, r"\begin{{equation}}")?;
+
+        self.indented(|p| {
+            writeln!(p.writer, r"\begin{{split}}")?;
+            p.indented(|p| {
+                // the last line doesn't need a line break
+                let last = equation.lines.len().saturating_sub(1);
+                for (i, line) in equation.lines.iter().enumerate() {
+                    if i < last {
+                        writeln!(p.writer, r"{} \\", line)?;
+                    } else {
+                        writeln!(p.writer, "{}", line)?;
+                    }
+                }
+                Ok(())
+            })?;
+            writeln!(p.writer, r"\end{{split}}")?;
+
+            if let Some(ref label) = equation.label {
+                writeln!(p.writer, r"\label{{{}}}", label)?;
+            }
+            Ok(())
+        })?;
+
+        writeln!(self.writer, r"\end{{equation}}")?;
 
-        writeln!(self.writer, r" \\")?;
         Ok(())
     }
 
     fn visit_align(&mut self, align: &Align) -> Result<(), Error> {
-        writeln!(self.writer, r"\begin{{align}}")?;
-
-        for item in align.iter() {
-            self.visit_equation(item)?;
+        let star = if align.is_numbered() { "" } else { "*" };
+        let env = format!("{}{}", align.environment().environment_name(), star);
+
+        let single = match align.environment() {
+            MathEnvironment::Equation | MathEnvironment::Multline => true,
+            MathEnvironment::Align | MathEnvironment::Gather => false,
+        };
+
+        if align.uses_subequations() {
+            writeln!(self.writer, r"\begin{{subequations}}")?;
+            self.indented(|p| {
+                if single {
+                    p.write_single_equation(align, &env)
+                } else {
+                    p.write_align_environment(align, &env)
+                }
+            })?;
+            writeln!(self.writer, r"\end{{subequations}}")?;
+        } else if single {
+            self.write_single_equation(align, &env)?;
+        } else {
+            self.write_align_environment(align, &env)?;
         }
 
-        writeln!(self.writer, r"\end{{align}}")?;
-
         Ok(())
     }
 }
@@ -234,7 +1002,10 @@ where
 mod tests {
     use self::ParagraphElement::*;
     use super::*;
-    use {Align, DocumentClass, Equation, ListKind, Paragraph, Section};
+    use {
+        Align, DocumentClass, Equation, FancyHeader, Figure, Image, ListKind, MathEnvironment,
+        PageGeometry, Paragraph, Placement, Section, SectionLevel, Theorem,
+    };
 
     #[test]
     fn create_simple_paragraph() {
@@ -287,14 +1058,46 @@ mod tests {
     }
 
     #[test]
-    fn inline_code() {
-        let should_be = "Hello $\\lambda$ World!\n";
+    fn paragraph_with_underlined_text() {
+        let should_be = "Hello \\underline{World}\n";
+        let mut buffer = Vec::new();
+
+        let mut para = Paragraph::new();
+        para.push_text("Hello ");
+        para.push(Underline(Box::new(Plain("World".to_string()))));
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_paragraph(&para).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn underlined_bold_text() {
+        let should_be = "\\underline{\\textbf{x}}\n";
+        let mut buffer = Vec::new();
+
+        let mut para = Paragraph::new();
+        para.push(ParagraphElement::underline(ParagraphElement::bold("x")));
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_paragraph(&para).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn strikeout_nested_inside_bold() {
+        let should_be = "Hello \\textbf{\\sout{World}}\n";
         let mut buffer = Vec::new();
 
         let mut para = Paragraph::new();
         para.push_text("Hello ")
-            .push(InlineMath(r"\lambda".to_string()))
-            .push_text(" World!");
+            .push(ParagraphElement::bold(ParagraphElement::strikeout("World")));
 
         {
             let mut printer = Printer::new(&mut buffer);
@@ -305,306 +1108,2406 @@ mod tests {
     }
 
     #[test]
-    fn preamble_with_author_and_title() {
-        let should_be = r#"\title{Sample Document}
-\author{Michael-F-Bryan}
-"#;
+    fn superscript_text() {
+        let should_be = "x\\textsuperscript{2}\n";
         let mut buffer = Vec::new();
 
-        let mut preamble = Preamble::default();
-        preamble.title("Sample Document").author("Michael-F-Bryan");
+        let mut para = Paragraph::new();
+        para.push_text("x").push(ParagraphElement::superscript("2"));
 
         {
             let mut printer = Printer::new(&mut buffer);
-            printer.visit_preamble(&preamble).unwrap();
+            printer.visit_paragraph(&para).unwrap();
         }
 
         assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
     }
 
     #[test]
-    fn preamble_with_title_and_package_imports() {
-        let should_be = r#"\usepackage{amsmath}
-\usepackage{graphics}
+    fn subscript_with_nested_bold() {
+        let should_be = "H\\textsubscript{\\textbf{2}}O\n";
+        let mut buffer = Vec::new();
 
-\title{Sample Document}
-"#;
+        let mut para = Paragraph::new();
+        para.push_text("H")
+            .push(ParagraphElement::subscript(ParagraphElement::bold("2")))
+            .push_text("O");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_paragraph(&para).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn link_with_plain_text() {
+        let should_be = "See \\href{https://www.rust-lang.org/}{the Rust website}\n";
         let mut buffer = Vec::new();
 
-        let mut preamble = Preamble::default();
-        preamble
-            .title("Sample Document")
-            .use_package("amsmath")
-            .use_package("graphics");
+        let mut para = Paragraph::new();
+        para.push_text("See ").push(ParagraphElement::link(
+            "https://www.rust-lang.org/",
+            "the Rust website",
+        ));
 
         {
             let mut printer = Printer::new(&mut buffer);
-            printer.visit_preamble(&preamble).unwrap();
+            printer.visit_paragraph(&para).unwrap();
         }
 
         assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
     }
 
     #[test]
-    fn preamble_with_newcommand() {
-        let should_be = r#"\newcommand{\Love}[2]{
-#1 loves #2
-}
-"#;
+    fn link_with_bold_text() {
+        let should_be = "\\href{https://docs.rs/}{\\textbf{docs}}\n";
         let mut buffer = Vec::new();
-        let mut preamble = Preamble::default();
-        preamble.new_command("Love", 2, "#1 loves #2");
-        
+
+        let mut para = Paragraph::new();
+        para.push(ParagraphElement::link(
+            "https://docs.rs/",
+            ParagraphElement::bold("docs"),
+        ));
+
         {
             let mut printer = Printer::new(&mut buffer);
-            printer.visit_preamble(&preamble).unwrap();
+            printer.visit_paragraph(&para).unwrap();
         }
 
         assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
     }
 
     #[test]
-    fn preamble_with_newcommand_with_default_argument() {
-        let should_be = r#"\newcommand{\Love}[3][likes]{
-#2 #1 #3
-}
-"#;
+    fn colored_text() {
+        let should_be = "\\textcolor{red}{warning}\n";
         let mut buffer = Vec::new();
-        let mut preamble = Preamble::default();
-        preamble.push(
-            PreambleElement::NewCommand {
-                name: String::from("Love"),
-                args_num: Some(3),
-                default_arg: Some(String::from("likes")),
-                definition: String::from("#2 #1 #3")
-            }
-        );
-        
+
+        let mut para = Paragraph::new();
+        para.push(ParagraphElement::colored("red", "warning"));
+
         {
             let mut printer = Printer::new(&mut buffer);
-            printer.visit_preamble(&preamble).unwrap();
+            printer.visit_paragraph(&para).unwrap();
         }
 
         assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
     }
 
     #[test]
-    fn render_empty_document() {
-        let should_be = r#"\documentclass{article}
-\begin{document}
-\end{document}
-"#;
+    fn colored_bold_text() {
+        let should_be = "\\textcolor{blue}{\\textbf{x}}\n";
         let mut buffer = Vec::new();
 
-        let doc = Document::new(DocumentClass::Article);
+        let mut para = Paragraph::new();
+        para.push(ParagraphElement::colored(
+            "blue",
+            ParagraphElement::bold("x"),
+        ));
 
         {
             let mut printer = Printer::new(&mut buffer);
-            printer.visit_document(&doc).unwrap();
+            printer.visit_paragraph(&para).unwrap();
         }
 
         assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
     }
 
     #[test]
-    fn render_enumerated_list() {
-        let should_be = "\\begin{enumerate}\n\\end{enumerate}\n";
+    fn simple_verb() {
+        let should_be = "Run \\verb|cargo build|\n";
         let mut buffer = Vec::new();
 
-        let list = List::new(ListKind::Enumerate);
+        let mut para = Paragraph::new();
+        para.push_text("Run ").push(Verb("cargo build".to_string()));
 
         {
             let mut printer = Printer::new(&mut buffer);
-            printer.visit_list(&list).unwrap();
+            printer.visit_paragraph(&para).unwrap();
         }
 
         assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
     }
 
     #[test]
-    fn render_empty_itemize_list() {
-        let should_be = "\\begin{itemize}\n\\end{itemize}\n";
+    fn verb_containing_pipe_uses_another_delimiter() {
+        let should_be = "\\verb!a|b!\n";
         let mut buffer = Vec::new();
 
-        let list = List::new(ListKind::Itemize);
+        let mut para = Paragraph::new();
+        para.push(Verb("a|b".to_string()));
 
         {
             let mut printer = Printer::new(&mut buffer);
-            printer.visit_list(&list).unwrap();
+            printer.visit_paragraph(&para).unwrap();
         }
 
         assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
     }
 
     #[test]
-    fn render_list_with_items() {
-        let should_be = r"\begin{itemize}
-\item This
-\item is
-\item a
-\item list!
-\end{itemize}
-";
+    fn verb_with_no_available_delimiter_is_an_error() {
         let mut buffer = Vec::new();
+        let element = Verb(VERB_DELIMITERS.iter().collect());
 
-        let mut list = List::new(ListKind::Itemize);
-        list.push("This").push("is").push("a").push("list!");
+        let mut printer = Printer::new(&mut buffer);
+        assert!(printer.visit_paragraph_element(&element).is_err());
+    }
+
+    #[test]
+    fn reference_a_label() {
+        let should_be = "see equation \\ref{eq:foo}\n";
+        let mut buffer = Vec::new();
+
+        let mut para = Paragraph::new();
+        para.push_text("see equation ")
+            .push(ParagraphElement::reference("eq:foo"));
 
         {
             let mut printer = Printer::new(&mut buffer);
-            printer.visit_list(&list).unwrap();
+            printer.visit_paragraph(&para).unwrap();
         }
 
         assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
     }
 
     #[test]
-    fn render_blank_section() {
-        let should_be = "\\section{First Section}\n";
+    fn page_reference() {
+        let should_be = "on page \\pageref{sec:intro}\n";
         let mut buffer = Vec::new();
 
-        let section = Section::new("First Section");
+        let mut para = Paragraph::new();
+        para.push_text("on page ")
+            .push(Pageref("sec:intro".to_string()));
 
         {
             let mut printer = Printer::new(&mut buffer);
-            printer.visit_section(&section).unwrap();
+            printer.visit_paragraph(&para).unwrap();
         }
 
         assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
     }
 
     #[test]
-    fn section_with_paragraphs() {
-        let should_be = r#"\section{First Section}
+    fn cite_single_key() {
+        let should_be = "As shown in \\cite{knuth84}\n";
+        let mut buffer = Vec::new();
 
-Lorem Ipsum...
+        let mut para = Paragraph::new();
+        para.push_text("As shown in ")
+            .push(ParagraphElement::cite(&["knuth84"]));
 
-Hello World!
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_paragraph(&para).unwrap();
+        }
 
-"#;
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn cite_multiple_keys() {
+        let should_be = "\\cite{knuth84,lamport94}\n";
         let mut buffer = Vec::new();
 
-        let mut section = Section::new("First Section");
-        section.push("Lorem Ipsum...").push("Hello World!");
+        let mut para = Paragraph::new();
+        para.push(ParagraphElement::cite(&["knuth84", "lamport94"]));
 
         {
             let mut printer = Printer::new(&mut buffer);
-            printer.visit_section(&section).unwrap();
+            printer.visit_paragraph(&para).unwrap();
         }
 
         assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
     }
 
     #[test]
-    fn render_empty_align() {
-        let should_be = "\\begin{align}\n\\end{align}\n";
+    fn cite_with_post_note() {
+        let should_be = "\\cite[p.~42]{knuth84}\n";
         let mut buffer = Vec::new();
 
-        let equations = Align::new();
+        let mut para = Paragraph::new();
+        para.push(Cite {
+            keys: vec!["knuth84".to_string()],
+            pre_note: None,
+            post_note: Some("p.~42".to_string()),
+        });
 
         {
             let mut printer = Printer::new(&mut buffer);
-            printer.visit_align(&equations).unwrap();
+            printer.visit_paragraph(&para).unwrap();
         }
 
         assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
     }
 
     #[test]
-    fn render_simple_equation() {
-        let should_be = "x &= y + \\sigma \\\\\n";
+    fn plain_text_is_unescaped_by_default() {
+        let should_be = "50% & $5\n";
         let mut buffer = Vec::new();
-        let eq = Equation::new(r"x &= y + \sigma");
+
+        let para = Paragraph::from("50% & $5");
 
         {
             let mut printer = Printer::new(&mut buffer);
-            printer.visit_equation(&eq).unwrap();
+            printer.visit_paragraph(&para).unwrap();
         }
 
         assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
     }
 
     #[test]
-    fn render_several_equations() {
-        let should_be = r"\begin{align}
-E &= m c^2 \label{eq:mass-energy-equivalence} \\
-y &= m x + c \\
-\end{align}
+    fn escape_every_special_character() {
+        let should_be = r"\& \% \$ \# \_ \{ \} \textasciitilde{} \textasciicircum{} \textbackslash{}
 ";
         let mut buffer = Vec::new();
 
-        let mut equations = Align::new();
+        let para = Paragraph::from(r"& % $ # _ { } ~ ^ \");
 
-        equations
-            .push(Equation::with_label(
-                "eq:mass-energy-equivalence",
-                "E &= m c^2",
-            ))
-            .push("y &= m x + c");
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.escape_special_chars(true);
+            printer.visit_paragraph(&para).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn inline_code() {
+        let should_be = "Hello $\\lambda$ World!\n";
+        let mut buffer = Vec::new();
+
+        let mut para = Paragraph::new();
+        para.push_text("Hello ")
+            .push(InlineMath(r"\lambda".to_string()))
+            .push_text(" World!");
 
         {
             let mut printer = Printer::new(&mut buffer);
-            printer.visit_align(&equations).unwrap();
+            printer.visit_paragraph(&para).unwrap();
         }
 
         assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
     }
 
     #[test]
-    fn equation_with_label() {
-        let should_be = "E &= m c^2 \\label{eq:mass-energy-equivalence} \\\\\n";
+    fn display_math() {
+        let should_be = "\\[x^2\\]\n";
         let mut buffer = Vec::new();
 
-        let mut eq = Equation::new("E &= m c^2");
-        eq.label("eq:mass-energy-equivalence");
+        let mut para = Paragraph::new();
+        para.push(DisplayMath("x^2".to_string()));
 
         {
             let mut printer = Printer::new(&mut buffer);
-            printer.visit_equation(&eq).unwrap();
+            printer.visit_paragraph(&para).unwrap();
         }
 
         assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
     }
 
     #[test]
-    fn equation_with_no_numbering() {
-        let should_be = "E &= m c^2 \\nonumber \\\\\n";
+    fn preamble_with_author_and_title() {
+        let should_be = r#"\title{Sample Document}
+\author{Michael-F-Bryan}
+"#;
         let mut buffer = Vec::new();
 
-        let mut eq = Equation::new("E &= m c^2");
-        eq.not_numbered();
+        let mut preamble = Preamble::default();
+        preamble.title("Sample Document").author("Michael-F-Bryan");
 
         {
             let mut printer = Printer::new(&mut buffer);
-            printer.visit_equation(&eq).unwrap();
+            printer.visit_preamble(&preamble).unwrap();
         }
 
         assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
     }
 
     #[test]
-    fn partial_document() {
-        let should_be = "";
+    fn preamble_with_author_title_and_date() {
+        let should_be = r#"\title{Sample Document}
+\author{Michael-F-Bryan}
+\date{1 January 2018}
+"#;
         let mut buffer = Vec::new();
-        let doc = Document::new(DocumentClass::Part);
+
+        let mut preamble = Preamble::default();
+        preamble
+            .title("Sample Document")
+            .author("Michael-F-Bryan")
+            .date("1 January 2018");
 
         {
             let mut printer = Printer::new(&mut buffer);
-            printer.visit_document(&doc).unwrap();
+            printer.visit_preamble(&preamble).unwrap();
         }
+
         assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
     }
 
     #[test]
-    fn input_statement() {
-        let should_be = "\\input{test.tex}\n";
+    fn preamble_with_multiple_authors() {
+        let should_be = "\\author{Alice \\and Bob}\n";
         let mut buffer = Vec::new();
-        let input = Element::Input("test.tex".into());
+
+        let mut preamble = Preamble::default();
+        preamble.add_author("Alice").add_author("Bob");
 
         {
             let mut printer = Printer::new(&mut buffer);
-            printer.visit_element(&input).unwrap()
+            printer.visit_preamble(&preamble).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn preamble_with_todays_date() {
+        let should_be = "\\date{\\today}\n";
+        let mut buffer = Vec::new();
+
+        let mut preamble = Preamble::default();
+        preamble.today();
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
         }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn preamble_with_empty_date() {
+        let should_be = "\\date{}\n";
+        let mut buffer = Vec::new();
+
+        let mut preamble = Preamble::default();
+        preamble.date("");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn preamble_with_title_and_package_imports() {
+        let should_be = r#"\usepackage{amsmath}
+\usepackage{graphics}
+
+\title{Sample Document}
+"#;
+        let mut buffer = Vec::new();
+
+        let mut preamble = Preamble::default();
+        preamble
+            .title("Sample Document")
+            .use_package("amsmath")
+            .use_package("graphics");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn preamble_with_package_options() {
+        let should_be = r#"\usepackage[margin=1in]{geometry}
+\usepackage{amsmath}
+"#;
+        let mut buffer = Vec::new();
+
+        let mut preamble = Preamble::default();
+        preamble
+            .use_package_with("geometry", "margin=1in")
+            .use_package("amsmath");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn preamble_with_uniform_margin() {
+        let should_be = "\\usepackage[margin=1in]{geometry}\n";
+        let mut buffer = Vec::new();
+
+        let mut preamble = Preamble::default();
+        preamble.geometry(PageGeometry {
+            margin: Some("1in".to_string()),
+            ..Default::default()
+        });
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn setting_geometry_again_replaces_it() {
+        let should_be = "\\usepackage[margin=2cm]{geometry}\n\\usepackage{amsmath}\n";
+        let mut buffer = Vec::new();
+
+        let mut preamble = Preamble::default();
+        preamble
+            .geometry(PageGeometry {
+                margin: Some("1in".to_string()),
+                ..Default::default()
+            })
+            .use_package("amsmath")
+            .geometry(PageGeometry {
+                margin: Some("2cm".to_string()),
+                ..Default::default()
+            });
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn preamble_with_asymmetric_margins() {
+        let should_be =
+            "\\usepackage[paper=a4paper,top=2cm,bottom=3cm,left=2.5cm,right=1.5cm]{geometry}\n";
+        let mut buffer = Vec::new();
+
+        let mut preamble = Preamble::default();
+        preamble.geometry(PageGeometry {
+            paper: Some("a4paper".to_string()),
+            top: Some("2cm".to_string()),
+            bottom: Some("3cm".to_string()),
+            left: Some("2.5cm".to_string()),
+            right: Some("1.5cm".to_string()),
+            ..Default::default()
+        });
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn preamble_with_newcommand_without_arguments() {
+        let should_be = r#"\newcommand{\R}{
+\mathbb{R}
+}
+"#;
+        let mut buffer = Vec::new();
+        let mut preamble = Preamble::default();
+        preamble.push(PreambleElement::NewCommand {
+            name: String::from("R"),
+            args_num: None,
+            default_arg: None,
+            definition: String::from(r"\mathbb{R}"),
+        });
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn preamble_with_newcommand() {
+        let should_be = r#"\newcommand{\Love}[2]{
+#1 loves #2
+}
+"#;
+        let mut buffer = Vec::new();
+        let mut preamble = Preamble::default();
+        preamble.new_command("Love", 2, "#1 loves #2");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn preamble_with_newcommand_with_default_argument() {
+        let should_be = r#"\newcommand{\Love}[3][likes]{
+#2 #1 #3
+}
+"#;
+        let mut buffer = Vec::new();
+        let mut preamble = Preamble::default();
+        preamble.push(PreambleElement::NewCommand {
+            name: String::from("Love"),
+            args_num: Some(3),
+            default_arg: Some(String::from("likes")),
+            definition: String::from("#2 #1 #3"),
+        });
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn preamble_with_renewcommand() {
+        let should_be = "\\renewcommand{\\abstractname}{Summary}\n";
+        let mut buffer = Vec::new();
+        let mut preamble = Preamble::default();
+        preamble.renew_command("abstractname", "Summary");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn preamble_with_newenvironment() {
+        let should_be = "\\newenvironment{boxed}{\\begin{center}}{\\end{center}}\n";
+        let mut buffer = Vec::new();
+        let mut preamble = Preamble::default();
+        preamble.new_environment("boxed", 0, r"\begin{center}", r"\end{center}");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn preamble_with_newenvironment_with_argument() {
+        let should_be = "\\newenvironment{titled}[1]{\\textbf{#1}}{}\n";
+        let mut buffer = Vec::new();
+        let mut preamble = Preamble::default();
+        preamble.new_environment("titled", 1, r"\textbf{#1}", "");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn preamble_with_newtheorem() {
+        let should_be = r#"\newtheorem{theorem}{Theorem}
+\newtheorem{lemma}{Lemma}[section]
+"#;
+        let mut buffer = Vec::new();
+        let mut preamble = Preamble::default();
+        preamble
+            .new_theorem("theorem", "Theorem")
+            .push(PreambleElement::NewTheorem {
+                env: String::from("lemma"),
+                display: String::from("Lemma"),
+                counter: Some(String::from("section")),
+            });
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn preamble_with_bib_resource() {
+        let should_be = "\\usepackage{biblatex}\n\\addbibresource{references.bib}\n";
+        let mut buffer = Vec::new();
+        let mut preamble = Preamble::default();
+        preamble
+            .use_package("biblatex")
+            .add_bib_resource("references.bib");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn render_empty_document() {
+        let should_be = r#"\documentclass{article}
+\begin{document}
+\end{document}
+"#;
+        let mut buffer = Vec::new();
+
+        let doc = Document::new(DocumentClass::Article);
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_document(&doc).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn render_document_with_class_options() {
+        let should_be = r#"\documentclass[12pt,a4paper]{report}
+\begin{document}
+\end{document}
+"#;
+        let mut buffer = Vec::new();
+
+        let mut doc = Document::new(DocumentClass::Report);
+        doc.add_class_option("12pt").add_class_option("a4paper");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_document(&doc).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn render_enumerated_list() {
+        let should_be = "\\begin{enumerate}\n\\end{enumerate}\n";
+        let mut buffer = Vec::new();
+
+        let list = List::new(ListKind::Enumerate);
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_list(&list).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn render_empty_itemize_list() {
+        let should_be = "\\begin{itemize}\n\\end{itemize}\n";
+        let mut buffer = Vec::new();
+
+        let list = List::new(ListKind::Itemize);
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_list(&list).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn render_list_with_items() {
+        let should_be = r"\begin{itemize}
+\item This
+\item is
+\item a
+\item list!
+\end{itemize}
+";
+        let mut buffer = Vec::new();
+
+        let mut list = List::new(ListKind::Itemize);
+        list.push("This").push("is").push("a").push("list!");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_list(&list).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn render_list_with_labeled_items() {
+        let should_be = r"\begin{itemize}
+\item[Q1] What is LaTeX?
+\item A typesetting system.
+\item[*] Starred
+\end{itemize}
+";
+        let mut buffer = Vec::new();
+
+        let mut list = List::new(ListKind::Itemize);
+        list.push_labeled("Q1", "What is LaTeX?")
+            .push("A typesetting system.")
+            .push_labeled("*", "Starred");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_list(&list).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn render_nested_list() {
+        let should_be = r"\begin{itemize}
+\item Fruit
+  \begin{itemize}
+  \item Apple
+  \item Banana
+  \end{itemize}
+\item Vegetables
+\end{itemize}
+";
+        let mut buffer = Vec::new();
+
+        let mut fruit = List::new(ListKind::Itemize);
+        fruit.push("Apple").push("Banana");
+        let mut list = List::new(ListKind::Itemize);
+        list.push("Fruit").push_list(fruit).push("Vegetables");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_list(&list).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn list_starting_with_a_sublist_gets_an_empty_item() {
+        let should_be = r"\begin{itemize}
+\item
+  \begin{itemize}
+  \item Apple
+  \end{itemize}
+\item Vegetables
+\end{itemize}
+";
+        let mut buffer = Vec::new();
+
+        let mut fruit = List::new(ListKind::Itemize);
+        fruit.push("Apple");
+        let mut list = List::new(ListKind::Itemize);
+        list.push_list(fruit).push("Vegetables");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_list(&list).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn render_blank_section() {
+        let should_be = "\\section{First Section}\n";
+        let mut buffer = Vec::new();
+
+        let section = Section::new("First Section");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_section(&section).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn render_unnumbered_section() {
+        let should_be = "\\section*{Acknowledgements}\n";
+        let mut buffer = Vec::new();
+
+        let mut section = Section::new("Acknowledgements");
+        section.numbered(false);
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_section(&section).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn render_each_section_level() {
+        let levels = vec![
+            (SectionLevel::Part, "\\part{Name}\n"),
+            (SectionLevel::Chapter, "\\chapter{Name}\n"),
+            (SectionLevel::Section, "\\section{Name}\n"),
+            (SectionLevel::Subsection, "\\subsection{Name}\n"),
+            (SectionLevel::Subsubsection, "\\subsubsection{Name}\n"),
+            (SectionLevel::Paragraph, "\\paragraph{Name}\n"),
+            (SectionLevel::Subparagraph, "\\subparagraph{Name}\n"),
+        ];
+
+        for (level, should_be) in levels {
+            let mut buffer = Vec::new();
+
+            let mut section = Section::new("Name");
+            section.level(level);
+
+            {
+                let mut printer = Printer::new(&mut buffer);
+                printer.visit_section(&section).unwrap();
+            }
+
+            assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+        }
+    }
+
+    #[test]
+    fn subsection_nested_in_section() {
+        let should_be = r#"\section{Outer}
+
+\subsection{Inner}
+
+Hello World!
+
+
+"#;
+        let mut buffer = Vec::new();
+
+        let mut inner = Section::new("Inner");
+        inner.level(SectionLevel::Subsection).push("Hello World!");
+        let mut outer = Section::new("Outer");
+        outer.push(inner);
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_section(&outer).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn chapter_containing_subsection() {
+        let should_be = r#"\chapter{Introduction}
+
+\subsection{Motivation}
+
+"#;
+        let mut buffer = Vec::new();
+
+        let mut subsection = Section::new("Motivation");
+        subsection.level(SectionLevel::Subsection);
+        let mut chapter = Section::new("Introduction");
+        chapter.level(SectionLevel::Chapter).push(subsection);
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_section(&chapter).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn section_with_label() {
+        let should_be = r#"\section{Method}\label{sec:method}
+
+Lorem Ipsum...
+
+"#;
+        let mut buffer = Vec::new();
+
+        let mut section = Section::new("Method");
+        section.label("sec:method").push("Lorem Ipsum...");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_section(&section).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn section_with_paragraphs() {
+        let should_be = r#"\section{First Section}
+
+Lorem Ipsum...
+
+Hello World!
+
+"#;
+        let mut buffer = Vec::new();
+
+        let mut section = Section::new("First Section");
+        section.push("Lorem Ipsum...").push("Hello World!");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_section(&section).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn render_empty_align() {
+        let should_be = "\\begin{align}\n\\end{align}\n";
+        let mut buffer = Vec::new();
+
+        let equations = Align::new();
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_align(&equations).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn render_simple_equation() {
+        let should_be = "x &= y + \\sigma \\\\\n";
+        let mut buffer = Vec::new();
+        let eq = Equation::new(r"x &= y + \sigma");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_equation(&eq).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn render_several_equations() {
+        let should_be = r"\begin{align}
+E &= m c^2 \label{eq:mass-energy-equivalence} \\
+y &= m x + c \\
+\end{align}
+";
+        let mut buffer = Vec::new();
+
+        let mut equations = Align::new();
+
+        equations
+            .push(Equation::with_label(
+                "eq:mass-energy-equivalence",
+                "E &= m c^2",
+            ))
+            .push("y &= m x + c");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_align(&equations).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn render_equations_in_gather() {
+        let should_be = r"\begin{gather}
+E = m c^2 \\
+y = m x + c \\
+\end{gather}
+";
+        let mut buffer = Vec::new();
+
+        let mut equations = Align::with_environment(MathEnvironment::Gather);
+        equations.push("E = m c^2").push("y = m x + c");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_align(&equations).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn render_equations_in_multline() {
+        let should_be = r"\begin{multline}
+E = m c^2 \\
+y = m x + c
+\end{multline}
+";
+        let mut buffer = Vec::new();
+
+        let mut equations = Align::with_environment(MathEnvironment::Multline);
+        equations.push("E = m c^2").push("y = m x + c");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_align(&equations).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn multline_with_a_single_label() {
+        let should_be = r"\begin{multline}
+a + b + c \\
++ d + e \label{eq:long}
+\end{multline}
+";
+        let mut buffer = Vec::new();
+
+        let mut equations = Align::with_environment(MathEnvironment::Multline);
+        equations
+            .push(Equation::with_label("eq:long", "a + b + c"))
+            .push("+ d + e");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_align(&equations).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn multline_rejects_multiple_labels() {
+        let mut equations = Align::with_environment(MathEnvironment::Multline);
+        equations
+            .push(Equation::with_label("first", "a + b"))
+            .push(Equation::with_label("second", "+ c"));
+
+        let mut printer = Printer::new(Vec::new());
+        assert!(printer.visit_align(&equations).is_err());
+    }
+
+    #[test]
+    fn render_a_single_equation() {
+        let should_be = r"\begin{equation}
+E = m c^2 \label{eq:emc2}
+\end{equation}
+";
+        let mut buffer = Vec::new();
+
+        let mut equations = Align::with_environment(MathEnvironment::Equation);
+        equations.push(Equation::with_label("eq:emc2", "E = m c^2"));
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_align(&equations).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn equation_environment_rejects_multiple_equations() {
+        let mut equations = Align::with_environment(MathEnvironment::Equation);
+        equations.push("E = m c^2").push("y = m x + c");
+
+        let mut printer = Printer::new(Vec::new());
+        assert!(printer.visit_align(&equations).is_err());
+    }
+
+    #[test]
+    fn render_unnumbered_align() {
+        let should_be = r"\begin{align*}
+E &= m c^2 \\
+y &= m x + c \\
+\end{align*}
+";
+        let mut buffer = Vec::new();
+
+        let mut not_numbered = Equation::new("y &= m x + c");
+        not_numbered.not_numbered();
+        let mut equations = Align::new();
+        equations.unnumbered().push("E &= m c^2").push(not_numbered);
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_align(&equations).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn render_align_in_subequations() {
+        let should_be = r"\begin{subequations}
+\begin{align}
+y &= m x + c \\
+y &= a x^2 + b x + c \\
+\end{align}
+\end{subequations}
+";
+        let mut buffer = Vec::new();
+
+        let mut equations = Align::new();
+        equations
+            .subequations(true)
+            .push("y &= m x + c")
+            .push("y &= a x^2 + b x + c");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_align(&equations).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn equation_with_label() {
+        let should_be = "E &= m c^2 \\label{eq:mass-energy-equivalence} \\\\\n";
+        let mut buffer = Vec::new();
+
+        let mut eq = Equation::new("E &= m c^2");
+        eq.label("eq:mass-energy-equivalence");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_equation(&eq).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn equation_with_tag() {
+        let should_be = "E &= m c^2 \\tag{$\\star$} \\\\\n";
+        let mut buffer = Vec::new();
+
+        let mut eq = Equation::new("E &= m c^2");
+        eq.tag(r"$\star$");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_equation(&eq).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn equation_with_label_and_tag() {
+        let should_be = "E &= m c^2 \\label{eq:emc2} \\tag{A} \\\\\n";
+        let mut buffer = Vec::new();
+
+        let mut eq = Equation::with_label("eq:emc2", "E &= m c^2");
+        eq.tag("A");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_equation(&eq).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn tag_overrides_no_numbering() {
+        let should_be = "E &= m c^2 \\tag{A} \\\\\n";
+        let mut buffer = Vec::new();
+
+        let mut eq = Equation::new("E &= m c^2");
+        eq.not_numbered().tag("A");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_equation(&eq).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn equation_with_no_numbering() {
+        let should_be = "E &= m c^2 \\nonumber \\\\\n";
+        let mut buffer = Vec::new();
+
+        let mut eq = Equation::new("E &= m c^2");
+        eq.not_numbered();
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_equation(&eq).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn partial_document() {
+        let should_be = "";
+        let mut buffer = Vec::new();
+        let doc = Document::new(DocumentClass::Part);
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_document(&doc).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn input_statement() {
+        let should_be = "\\input{test.tex}\n";
+        let mut buffer = Vec::new();
+        let input = Element::Input("test.tex".into());
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_element(&input).unwrap()
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn abstract_with_two_paragraphs() {
+        let should_be = r"\begin{abstract}
+We present a new method.
+
+It works well.
+\end{abstract}
+";
+        let mut buffer = Vec::new();
+        let element = Element::Abstract(vec![
+            Paragraph::from("We present a new method."),
+            Paragraph::from("It works well."),
+        ]);
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_element(&element).unwrap()
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn image_with_width() {
+        let should_be = "\\includegraphics[width=0.5\\textwidth]{cat.png}\n";
+        let mut buffer = Vec::new();
+
+        let mut image = Image::new("cat.png");
+        image.width(r"0.5\textwidth");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_image(&image).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn figure_with_caption_and_label() {
+        let should_be = r"\begin{figure}
+\centering
+\includegraphics[height=4cm,scale=0.5]{plots/results.pdf}
+\caption{Experimental results}
+\label{fig:results}
+\end{figure}
+";
+        let mut buffer = Vec::new();
+
+        let mut image = Image::new("plots/results.pdf");
+        image.height("4cm").scale(0.5);
+        let mut figure = Figure::new(image);
+        figure.caption("Experimental results").label("fig:results");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_element(&Element::Figure(figure)).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn figure_with_here_or_top_placement() {
+        let should_be = r"\begin{figure}[ht]
+\centering
+\includegraphics{cat.png}
+\end{figure}
+";
+        let mut buffer = Vec::new();
+
+        let mut figure = Figure::new(Image::new("cat.png"));
+        figure.placement(Placement {
+            here: true,
+            top: true,
+            ..Default::default()
+        });
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_figure(&figure).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn figure_with_an_empty_placement() {
+        let should_be = r"\begin{figure}
+\centering
+\includegraphics{cat.png}
+\end{figure}
+";
+        let mut buffer = Vec::new();
+
+        let mut figure = Figure::new(Image::new("cat.png"));
+        figure.placement(Placement::default());
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_figure(&figure).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn figure_placed_exactly_here() {
+        let should_be = r"\begin{figure}[H]
+\centering
+\includegraphics{cat.png}
+\end{figure}
+";
+        let mut buffer = Vec::new();
+
+        let mut figure = Figure::new(Image::new("cat.png"));
+        figure.placement(Placement {
+            exactly_here: true,
+            top: true,
+            ..Default::default()
+        });
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_figure(&figure).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn theorem_with_title() {
+        let should_be = r"\begin{theorem}[Pythagoras]
+In a right triangle, $a^2 + b^2 = c^2$.
+
+This is a second paragraph.
+\end{theorem}
+";
+        let mut buffer = Vec::new();
+
+        let mut theorem = Theorem::new("theorem");
+        theorem
+            .title("Pythagoras")
+            .push("In a right triangle, $a^2 + b^2 = c^2$.")
+            .push("This is a second paragraph.");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_element(&Element::Theorem(theorem)).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn bibliography() {
+        let should_be = "\\bibliographystyle{plain}\n\\bibliography{references}\n";
+        let mut buffer = Vec::new();
+        let bibliography = Element::Bibliography {
+            style: "plain".into(),
+            sources: "references".into(),
+        };
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_element(&bibliography).unwrap()
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn print_bibliography() {
+        let should_be = "\\printbibliography\n";
+        let mut buffer = Vec::new();
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_element(&Element::PrintBibliography).unwrap()
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn bare_listing() {
+        let should_be = "\\begin{lstlisting}\nx = 1\n\\end{lstlisting}\n";
+        let mut buffer = Vec::new();
+        let listing = Listing::new("x = 1");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_listing(&listing).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn listing_with_language_and_caption() {
+        let should_be = "\\begin{lstlisting}[language={Python},caption={Hello World}]\nprint(\"Hello, World!\")\n\\end{lstlisting}\n";
+        let mut buffer = Vec::new();
+        let mut listing = Listing::new("print(\"Hello, World!\")");
+        listing.language("Python").caption("Hello World");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_listing(&listing).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn listing_caption_with_a_comma() {
+        let should_be = "\\begin{lstlisting}[caption={Hello, World},label={lst:hello}]\nx = 1\n\\end{lstlisting}\n";
+        let mut buffer = Vec::new();
+        let mut listing = Listing::new("x = 1");
+        listing.caption("Hello, World").label("lst:hello");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_listing(&listing).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn minted_python_listing() {
+        let should_be = "\\begin{minted}[linenos=true,fontsize=\\small]{python}\ndef add(a, b):\n    return a + b\n\\end{minted}\n";
+        let mut buffer = Vec::new();
+        let mut listing = MintedListing::new("python", "def add(a, b):\n    return a + b");
+        listing
+            .option("linenos", "true")
+            .option("fontsize", r"\small");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_element(&listing.into()).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn center_a_paragraph_and_a_list() {
+        let should_be = "\\begin{center}\nHello World\n\n\\begin{itemize}\n\\item Apple\n\\end{itemize}\n\\end{center}\n";
+        let mut buffer = Vec::new();
+        let mut list = List::new(ListKind::Itemize);
+        list.push("Apple");
+        let center = Element::Center(vec!["Hello World".into(), list.into()]);
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_element(&center).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn horizontal_rule() {
+        let should_be = "\\par\\noindent\\rule{\\textwidth}{0.4pt}\n";
+        let mut buffer = Vec::new();
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_element(&Element::HorizontalRule).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn rule_with_custom_width() {
+        let should_be = "\\par\\noindent\\rule{0.5\\textwidth}{1pt}\n";
+        let mut buffer = Vec::new();
+        let rule = Element::Rule {
+            width: r"0.5\textwidth".to_string(),
+            thickness: "1pt".to_string(),
+        };
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_element(&rule).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn vertical_space() {
+        let should_be = "\\vspace{2\\baselineskip}\n";
+        let mut buffer = Vec::new();
+        let space = Element::VSpace(r"2\baselineskip".to_string());
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_element(&space).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn horizontal_space() {
+        let should_be = "Left\\hspace{\\fill}Right\n";
+        let mut buffer = Vec::new();
+        let mut para = Paragraph::new();
+        para.push_text("Left")
+            .push(HSpace(r"\fill".to_string()))
+            .push_text("Right");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_paragraph(&para).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn line_break_inside_paragraph() {
+        let should_be = "First line\\\\Second line\n";
+        let mut buffer = Vec::new();
+        let mut para = Paragraph::new();
+        para.push_text("First line")
+            .push(LineBreak)
+            .push_text("Second line");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_paragraph(&para).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn line_break_with_extra_space() {
+        let should_be = "First line\\\\[1em]Second line\n";
+        let mut buffer = Vec::new();
+        let mut para = Paragraph::new();
+        para.push_text("First line")
+            .push(LineBreakWith("1em".to_string()))
+            .push_text("Second line");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_paragraph(&para).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn indent_nested_list() {
+        let flat = r"\begin{itemize}
+\item Fruit
+  \begin{itemize}
+  \item Apple
+  \end{itemize}
+\end{itemize}
+";
+        let indented = r"\begin{itemize}
+    \item Fruit
+    \begin{itemize}
+        \item Apple
+    \end{itemize}
+\end{itemize}
+";
+        let mut fruit = List::new(ListKind::Itemize);
+        fruit.push("Apple");
+        let mut list = List::new(ListKind::Itemize);
+        list.push("Fruit").push_list(fruit);
+
+        let mut buffer = Vec::new();
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_list(&list).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), flat);
+
+        let mut buffer = Vec::new();
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.indent_environments(4).visit_list(&list).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), indented);
+    }
+
+    #[test]
+    fn indentation_skips_listing_code() {
+        let should_be = "\\begin{center}\n  \\begin{lstlisting}\nfor x in y:\n    pass\n  \\end{lstlisting}\n\\end{center}\n";
+        let mut buffer = Vec::new();
+        let listing = Listing::new("for x in y:\n    pass");
+        let center = Element::Center(vec![listing.into()]);
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer
+                .indent_environments(2)
+                .visit_element(&center)
+                .unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn indentation_skips_raw_environment_lines() {
+        let should_be = "\\begin{center}\n  \\begin{verbatim}\nfn main() {\n    run();\n}\n  \\end{verbatim}\n\\end{center}\n";
+        let mut buffer = Vec::new();
+        let verbatim = Element::from(("verbatim", vec!["fn main() {", "    run();", "}"]));
+        let center = Element::Center(vec![verbatim]);
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer
+                .indent_environments(2)
+                .visit_element(&center)
+                .unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn indentation_skips_the_tikz_picture_body() {
+        let should_be = "\\begin{center}\n  \\begin{tikzpicture}\n\\draw (0,0) -- (1,1);\n  \\end{tikzpicture}\n\\end{center}\n";
+        let mut buffer = Vec::new();
+        let picture = Element::TikzPicture {
+            options: None,
+            body: r"\draw (0,0) -- (1,1);".to_string(),
+        };
+        let center = Element::Center(vec![picture]);
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer
+                .indent_environments(2)
+                .visit_element(&center)
+                .unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn print_document_to_a_writer() {
+        let mut doc = Document::new(DocumentClass::Article);
+        doc.push("Hello World");
+        let mut buffer = Vec::new();
+
+        print_to(&doc, &mut buffer).unwrap();
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), print(&doc).unwrap());
+        assert!(print(&doc).unwrap().contains("Hello World\n"));
+    }
+
+    #[test]
+    fn display_a_document() {
+        let mut doc = Document::new(DocumentClass::Article);
+        doc.push("Hello World");
+
+        assert_eq!(doc.to_string(), print(&doc).unwrap());
+    }
+
+    #[test]
+    fn intertext_between_equations() {
+        let should_be = r"\begin{align}
+y &= mx + c \\
+\intertext{where $m$ is the gradient, and}
+c &= 0 \\
+\end{align}
+";
+        let mut buffer = Vec::new();
+        let mut align = Align::new();
+        align
+            .push("y &= mx + c")
+            .push_intertext("where $m$ is the gradient, and")
+            .push("c &= 0");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_align(&align).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn two_line_split_equation() {
+        let should_be = r"\begin{equation}
+\begin{split}
+(a + b)^2 &= (a + b)(a + b) \\
+&= a^2 + 2ab + b^2
+\end{split}
+\label{eq:square}
+\end{equation}
+";
+        let mut buffer = Vec::new();
+        let mut eq = SplitEquation::new();
+        eq.push("(a + b)^2 &= (a + b)(a + b)")
+            .push("&= a^2 + 2ab + b^2")
+            .label("eq:square");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_element(&eq.into()).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn align_without_trailing_backslash() {
+        let should_be = r"\begin{align}
+y &= mx + c \\
+E &= m c^2
+\end{align}
+";
+        let mut buffer = Vec::new();
+        let mut align = Align::new();
+        align
+            .push("y &= mx + c")
+            .push("E &= m c^2")
+            .trailing_backslash(false);
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_align(&align).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn align_ending_with_intertext_without_trailing_backslash() {
+        let should_be = r"\begin{align}
+y &= mx + c \\
+E &= m c^2
+\intertext{where $c$ is the speed of light.}
+\end{align}
+";
+        let mut buffer = Vec::new();
+        let mut align = Align::new();
+        align
+            .push("y &= mx + c")
+            .push("E &= m c^2")
+            .push_intertext("where $c$ is the speed of light.")
+            .trailing_backslash(false);
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_align(&align).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn bold_italic_text() {
+        let should_be = "\\textbf{\\textit{Important}}\n";
+        let mut buffer = Vec::new();
+        let mut para = Paragraph::new();
+        para.push(ParagraphElement::bold_italic("Important"));
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_paragraph(&para).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn small_caps_text() {
+        let should_be = "\\textsc{NASA} launched\n";
+        let mut buffer = Vec::new();
+        let mut para = Paragraph::new();
+        para.push(ParagraphElement::small_caps("NASA"))
+            .push_text(" launched");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_paragraph(&para).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn emphasis_inside_italics() {
+        let should_be = "\\textit{\\emph{really}}\n";
+        let mut buffer = Vec::new();
+        let mut para = Paragraph::new();
+        para.push(ParagraphElement::italic(ParagraphElement::emph("really")));
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_paragraph(&para).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn escaped_monospace_text() {
+        let should_be = "Call \\texttt{\\textbf{my\\_function}}\n";
+        let mut buffer = Vec::new();
+        let mut para = Paragraph::new();
+        para.push_text("Call ")
+            .push(ParagraphElement::monospace(ParagraphElement::bold(
+                "my_function",
+            )));
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer
+                .escape_special_chars(true)
+                .visit_paragraph(&para)
+                .unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn section_created_with_paragraphs() {
+        let should_be = "\\section{Intro}\n\nFirst\n\nSecond\n\n";
+        let mut buffer = Vec::new();
+        let paragraphs = vec![Paragraph::from("First"), Paragraph::from("Second")];
+        let section = Section::with_elements("Intro", paragraphs);
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_section(&section).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn duplicate_package_imports_are_ignored() {
+        let should_be = "\\usepackage{amsmath}\n\\usepackage[fleqn]{amsmath}\n";
+        let mut buffer = Vec::new();
+        let mut preamble = Preamble::default();
+        preamble
+            .use_package("amsmath")
+            .use_package("amsmath")
+            .use_package_with("amsmath", "fleqn")
+            .use_package_with("amsmath", "fleqn");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn preamble_sorted_on_render() {
+        let should_be = r"\usepackage{amsmath}
+\usepackage{xcolor}
+\newcommand{\R}[0]{
+\mathbb{R}
+}
+\newtheorem{theorem}{Theorem}
+\setlength{\parskip}{1em}
+";
+        let mut buffer = Vec::new();
+        let mut preamble = Preamble::default();
+        preamble
+            .push(PreambleElement::UserDefined(
+                r"\setlength{\parskip}{1em}".to_string(),
+            ))
+            .new_command("R", 0, r"\mathbb{R}")
+            .use_package("amsmath")
+            .new_theorem("theorem", "Theorem")
+            .use_package("xcolor")
+            .sort_on_render(true);
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn preamble_with_hypersetup() {
+        let should_be =
+            "\\usepackage{hyperref}\n\\hypersetup{colorlinks={true},pdftitle={My Report}}\n";
+        let mut buffer = Vec::new();
+        let mut preamble = Preamble::default();
+        preamble
+            .use_package("hyperref")
+            .hypersetup(&[("colorlinks", "true"), ("pdftitle", "My Report")]);
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn pdf_metadata_uses_a_single_hypersetup() {
+        let should_be = "\\usepackage{hyperref}\n\\hypersetup{pdftitle={Final Report},pdfauthor={Michael},pdfsubject={Physics},pdfkeywords={latex, rust}}\n";
+        let mut buffer = Vec::new();
+        let mut preamble = Preamble::default();
+        preamble
+            .use_package("hyperref")
+            .pdf_title("Draft")
+            .pdf_author("Michael")
+            .pdf_subject("Physics")
+            .pdf_keywords(&["latex", "rust"])
+            .pdf_title("Final Report");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn pdf_metadata_extends_existing_hypersetup() {
+        let should_be = "\\hypersetup{colorlinks={true},pdftitle={Report}}\n";
+        let mut buffer = Vec::new();
+        let mut preamble = Preamble::default();
+        preamble
+            .hypersetup(&[("colorlinks", "true")])
+            .pdf_title("Report");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn preamble_with_one_graphics_path() {
+        let should_be = "\\graphicspath{{figures/}}\n";
+        let mut buffer = Vec::new();
+        let mut preamble = Preamble::default();
+        preamble.graphics_path(&["figures/"]);
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn preamble_with_two_graphics_paths() {
+        let should_be = "\\graphicspath{{figures/}{images/plots/}}\n";
+        let mut buffer = Vec::new();
+        let mut preamble = Preamble::default();
+        preamble.graphics_path(&["figures", "images/plots/"]);
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn preamble_with_include_only() {
+        let should_be = "\\includeonly{introduction,results}\n";
+        let mut buffer = Vec::new();
+        let mut preamble = Preamble::default();
+        preamble.include_only(&["introduction", "results"]);
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn two_column_block() {
+        let should_be =
+            "\\begin{multicols}{2}\nFirst paragraph\n\nSecond paragraph\n\\end{multicols}\n";
+        let mut buffer = Vec::new();
+        let multicols = Element::MultiCols {
+            columns: 2,
+            body: vec!["First paragraph".into(), "Second paragraph".into()],
+        };
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_element(&multicols).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn half_width_minipage() {
+        let should_be = "\\begin{minipage}{0.5\\textwidth}\nSome text\n\\end{minipage}\n";
+        let mut buffer = Vec::new();
+        let minipage = Element::Minipage {
+            width: r"0.5\textwidth".to_string(),
+            body: vec!["Some text".into()],
+        };
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_element(&minipage).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn multi_line_comment() {
+        let should_be = "% TODO: proof read this section\n%\n% Remember to add the figures\n";
+        let mut buffer = Vec::new();
+        let comment = Element::Comment(
+            "TODO: proof read this section\n\nRemember to add the figures".to_string(),
+        );
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_element(&comment).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn empty_comment() {
+        let mut buffer = Vec::new();
+        let comment = Element::Comment(String::new());
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_element(&comment).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), "%\n");
+    }
+
+    #[test]
+    fn preamble_with_set_length() {
+        let should_be = "\\setlength{\\parindent}{0pt}\n\\setlength{\\parskip}{1em}\n";
+        let mut buffer = Vec::new();
+        let mut preamble = Preamble::default();
+        preamble
+            .set_length("parindent", "0pt")
+            .set_length(r"\parskip", "1em");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn preamble_with_new_counters() {
+        let should_be = "\\newcounter{exercise}\n\\newcounter{question}[section]\n";
+        let mut buffer = Vec::new();
+        let mut preamble = Preamble::default();
+        preamble
+            .new_counter("exercise")
+            .push(PreambleElement::NewCounter {
+                name: "question".to_string(),
+                within: Some("section".to_string()),
+            });
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn set_counter() {
+        let should_be = "\\setcounter{page}{-1}\n";
+        let mut buffer = Vec::new();
+        let element = Element::SetCounter {
+            name: "page".to_string(),
+            value: -1,
+        };
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_element(&element).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn preamble_with_centered_page_number_footer() {
+        let should_be =
+            "\\usepackage{fancyhdr}\n\\pagestyle{fancy}\n\\fancyhf{}\n\\fancyfoot[C]{\\thepage}\n";
+        let mut buffer = Vec::new();
+        let mut header = FancyHeader::new();
+        header.foot_center(r"\thepage");
+        let mut preamble = Preamble::default();
+        preamble.fancy_header(&header);
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn preamble_with_one_and_a_half_line_spacing() {
+        let should_be = "\\usepackage{setspace}\n\\onehalfspacing\n";
+        let mut buffer = Vec::new();
+        let mut preamble = Preamble::default();
+        preamble.line_spacing(1.5);
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn preamble_with_double_line_spacing() {
+        let should_be = "\\usepackage{setspace}\n\\doublespacing\n";
+        let mut buffer = Vec::new();
+        let mut preamble = Preamble::default();
+        preamble.line_spacing(2.0);
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn preamble_with_custom_line_spacing() {
+        let should_be = "\\usepackage{setspace}\n\\setstretch{1.25}\n";
+        let mut buffer = Vec::new();
+        let mut preamble = Preamble::default();
+        preamble.line_spacing(1.25);
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn setting_line_spacing_again_replaces_it() {
+        let should_be = "\\usepackage{setspace}\n\\doublespacing\n";
+        let mut buffer = Vec::new();
+        let mut preamble = Preamble::default();
+        preamble.line_spacing(1.25).line_spacing(2.0);
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_preamble(&preamble).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn appendix_comes_before_lettered_sections() {
+        let should_be = "\\appendix\n\\section{Raw Data}\n";
+        let mut doc = Document::new(DocumentClass::Part);
+        doc.push(Element::Appendix).push(Section::new("Raw Data"));
+
+        let rendered = print(&doc).unwrap();
+        assert_eq!(rendered, should_be);
+    }
+
+    #[test]
+    fn list_of_figures() {
+        let mut buffer = Vec::new();
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_element(&Element::ListOfFigures).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), "\\listoffigures\n");
+    }
+
+    #[test]
+    fn list_of_tables() {
+        let mut buffer = Vec::new();
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_element(&Element::ListOfTables).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), "\\listoftables\n");
+    }
+
+    #[test]
+    fn book_with_matter_divisions() {
+        let should_be = "\\frontmatter\n\\tableofcontents\n\\mainmatter\n\\backmatter\n";
+        let mut doc = Document::new(DocumentClass::Part);
+        doc.push(Element::FrontMatter)
+            .push(Element::TableOfContents)
+            .push(Element::MainMatter)
+            .push(Element::BackMatter);
+
+        let rendered = print(&doc).unwrap();
+        assert_eq!(rendered, should_be);
+    }
+
+    #[test]
+    fn environment_with_an_argument() {
+        let should_be = "\\begin{tabular}{lll}\na & b & c \\\\\n\\end{tabular}\n";
+        let mut buffer = Vec::new();
+        let element = Element::EnvironmentWith {
+            name: "tabular".to_string(),
+            options: Vec::new(),
+            args: vec!["lll".to_string()],
+            lines: vec![r"a & b & c \\".to_string()],
+        };
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_element(&element).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn environment_with_options_and_arguments() {
+        let should_be = "\\begin{wrapfigure}[10]{r}{0.5\\textwidth}\n\\end{wrapfigure}\n";
+        let mut buffer = Vec::new();
+        let element = Element::EnvironmentWith {
+            name: "wrapfigure".to_string(),
+            options: vec!["10".to_string()],
+            args: vec!["r".to_string(), r"0.5\textwidth".to_string()],
+            lines: Vec::new(),
+        };
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_element(&element).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn list_inside_a_framed_block() {
+        let should_be = "\\begin{framed}\nChecklist\n\n\\begin{itemize}\n\\item Apple\n\\end{itemize}\n\\end{framed}\n";
+        let mut buffer = Vec::new();
+        let mut list = List::new(ListKind::Itemize);
+        list.push("Apple");
+        let block = Element::Block {
+            name: "framed".to_string(),
+            body: vec!["Checklist".into(), list.into()],
+        };
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_element(&block).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn tikz_picture_with_a_line() {
+        let should_be =
+            "\\begin{tikzpicture}[scale=2]\n\\draw (0,0) -- (1,1);\n\\end{tikzpicture}\n";
+        let mut buffer = Vec::new();
+        let picture = Element::TikzPicture {
+            options: Some("scale=2".to_string()),
+            body: r"\draw (0,0) -- (1,1);".to_string(),
+        };
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_element(&picture).unwrap();
+        }
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn ordinal_numbers() {
+        let inputs = vec![
+            (1, "1\\textsuperscript{st}"),
+            (2, "2\\textsuperscript{nd}"),
+            (3, "3\\textsuperscript{rd}"),
+            (11, "11\\textsuperscript{th}"),
+            (12, "12\\textsuperscript{th}"),
+            (13, "13\\textsuperscript{th}"),
+            (21, "21\\textsuperscript{st}"),
+            (22, "22\\textsuperscript{nd}"),
+        ];
+
+        for (n, should_be) in inputs {
+            let mut buffer = Vec::new();
+            {
+                let mut printer = Printer::new(&mut buffer);
+                printer
+                    .visit_paragraph_element(&ParagraphElement::ordinal(n))
+                    .unwrap();
+            }
+            assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+        }
+    }
+
+    #[test]
+    fn url_is_displayed_as_is() {
+        let should_be = "See \\url{https://example.com/a_b%20c}\n";
+        let mut buffer = Vec::new();
+
+        let mut para = Paragraph::new();
+        para.push_text("See ")
+            .push(ParagraphElement::url("https://example.com/a_b%20c"));
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_paragraph(&para).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn non_breaking_space_is_inline() {
+        let should_be = "Figure~\\ref{fig:plot}\n";
+        let mut buffer = Vec::new();
+
+        let mut para = Paragraph::new();
+        para.push_text("Figure")
+            .push(ParagraphElement::NonBreakingSpace)
+            .push(ParagraphElement::reference("fig:plot"));
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_paragraph(&para).unwrap();
+        }
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
+    }
+
+    #[test]
+    fn thin_space_is_inline() {
+        let should_be = "5\\,kg\n";
+        let mut buffer = Vec::new();
+
+        let mut para = Paragraph::new();
+        para.push_text("5")
+            .push(ParagraphElement::ThinSpace)
+            .push_text("kg");
+
+        {
+            let mut printer = Printer::new(&mut buffer);
+            printer.visit_paragraph(&para).unwrap();
+        }
+
         assert_eq!(String::from_utf8(buffer).unwrap(), should_be);
     }
 }
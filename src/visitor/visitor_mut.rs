@@ -0,0 +1,341 @@
+use document::{Document, DocumentClass, Element, Preamble};
+use equations::{Align, Equation, SplitEquation};
+use failure::Error;
+use figure::{Figure, Image};
+use listing::{Listing, MintedListing};
+use lists::{Item, List};
+use paragraph::{Paragraph, ParagraphElement};
+use section::Section;
+use theorem::Theorem;
+
+/// The mutable counterpart to `Visitor`, letting you walk a `Document` and
+/// modify its nodes in-place.
+///
+/// # Examples
+///
+/// This pass will prefix the label of every equation in a document with
+/// `eq:`.
+///
+/// ```rust
+/// # extern crate failure;
+/// # extern crate latex;
+/// use failure::Error;
+/// use latex::{Document, DocumentClass, Equation, VisitorMut};
+///
+/// struct PrefixLabels;
+///
+/// impl VisitorMut for PrefixLabels {
+///     fn visit_equation(&mut self, equation: &mut Equation) -> Result<(), Error> {
+///         if let Some(label) = equation.get_label().map(|l| format!("eq:{}", l)) {
+///             equation.label(&label);
+///         }
+///
+///         Ok(())
+///     }
+/// }
+///
+/// # fn run() -> Result<(), Error> {
+/// let mut doc = Document::new(DocumentClass::Article);
+/// // add some equations to the document...
+///
+/// PrefixLabels.visit_document(&mut doc)?;
+/// # Ok(())
+/// # }
+/// # fn main() { run().unwrap() }
+/// ```
+#[allow(unused_variables)]
+pub trait VisitorMut {
+    /// Visit the root `Document` node, then recursively visit the preamble and
+    /// each element in the `Document`.
+    fn visit_document(&mut self, doc: &mut Document) -> Result<(), Error> {
+        if doc.class != DocumentClass::Part {
+            self.visit_preamble(&mut doc.preamble)?;
+        }
+
+        for element in doc.iter_mut() {
+            self.visit_element(element)?;
+        }
+
+        Ok(())
+    }
+
+    /// Visit a single `Element` node, dispatching to the more specific
+    /// `visit_*()` methods.
+    ///
+    /// > **Note:** You probably don't want to implement this one yourself. If
+    /// > you forget to recursively visit each and every variant of `Element`
+    /// > you may end up accidentally ignoring half your document!
+    fn visit_element(&mut self, elem: &mut Element) -> Result<(), Error> {
+        match *elem {
+            Element::Para(ref mut p) => self.visit_paragraph(p)?,
+            Element::Section(ref mut s) => self.visit_section(s)?,
+            Element::UserDefined(ref mut s) => self.visit_user_defined_line(s)?,
+            Element::Align(ref mut equations) => self.visit_align(equations)?,
+            Element::SplitEquation(ref mut eq) => self.visit_split_equation(eq)?,
+
+            Element::Environment(ref mut name, ref mut lines)
+            | Element::EnvironmentWith {
+                ref mut name,
+                ref mut lines,
+                ..
+            } => self.visit_custom_environment(name, lines)?,
+            Element::List(ref mut list) => self.visit_list(list)?,
+            Element::Input(ref mut s) => self.visit_input(s)?,
+            Element::Abstract(ref mut paragraphs) => {
+                for paragraph in paragraphs {
+                    self.visit_paragraph(paragraph)?;
+                }
+            }
+            Element::Figure(ref mut figure) => self.visit_figure(figure)?,
+            Element::Theorem(ref mut theorem) => self.visit_theorem(theorem)?,
+            Element::Listing(ref mut listing) => self.visit_listing(listing)?,
+            Element::MintedListing(ref mut listing) => self.visit_minted_listing(listing)?,
+            Element::MultiCols {
+                ref mut columns,
+                body: ref mut elements,
+            } => self.visit_multicols(columns, elements)?,
+            Element::TikzPicture {
+                ref mut options,
+                ref mut body,
+            } => self.visit_tikz_picture(options, body)?,
+            Element::PrintBibliography => self.visit_print_bibliography()?,
+            Element::Center(ref mut elements)
+            | Element::Minipage {
+                body: ref mut elements,
+                ..
+            }
+            | Element::Block {
+                body: ref mut elements,
+                ..
+            } => {
+                for element in elements {
+                    self.visit_element(element)?;
+                }
+            }
+
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Visit a document's `Preamble`.
+    fn visit_preamble(&mut self, preamble: &mut Preamble) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Visit an element in a `Paragraph` (e.g. `Italic`, `InlineCode`), then
+    /// recursively visit the element it wraps (if any).
+    fn visit_paragraph_element(&mut self, element: &mut ParagraphElement) -> Result<(), Error> {
+        if let Some(child) = element.children_mut() {
+            self.visit_paragraph_element(child)?;
+        }
+
+        Ok(())
+    }
+
+    /// Visit a user defined line.
+    fn visit_user_defined_line(&mut self, line: &mut String) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Visit a input element.
+    fn visit_input(&mut self, input: &mut String) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Visit a paragraph, and every `ParagraphElement` in it.
+    fn visit_paragraph(&mut self, paragraph: &mut Paragraph) -> Result<(), Error> {
+        for elem in &mut paragraph.elements {
+            self.visit_paragraph_element(elem)?;
+        }
+
+        Ok(())
+    }
+
+    /// Visit a `Section` and then recursively visit each of its `Element`s.
+    fn visit_section(&mut self, section: &mut Section) -> Result<(), Error> {
+        for elem in section.iter_mut() {
+            self.visit_element(elem)?;
+        }
+
+        Ok(())
+    }
+
+    /// Visit a `Theorem` and then recursively visit each of its `Element`s.
+    fn visit_theorem(&mut self, theorem: &mut Theorem) -> Result<(), Error> {
+        for elem in theorem.iter_mut() {
+            self.visit_element(elem)?;
+        }
+
+        Ok(())
+    }
+
+    /// Visit an `Align` block and then recursively visit each equation in the
+    /// block.
+    fn visit_align(&mut self, align: &mut Align) -> Result<(), Error> {
+        for equation in align.iter_mut() {
+            self.visit_equation(equation)?;
+        }
+
+        Ok(())
+    }
+
+    /// Visit a single `Equation`.
+    fn visit_equation(&mut self, equation: &mut Equation) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Visit a `SplitEquation`.
+    fn visit_split_equation(&mut self, equation: &mut SplitEquation) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Visit a `List` and all of its items.
+    fn visit_list(&mut self, list: &mut List) -> Result<(), Error> {
+        for item in list.iter_mut() {
+            self.visit_list_item(item)?;
+        }
+
+        Ok(())
+    }
+
+    /// Visit a single list item, recursively visiting any nested list.
+    fn visit_list_item(&mut self, item: &mut Item) -> Result<(), Error> {
+        if let Item::Sublist(ref mut list) = *item {
+            self.visit_list(list)?;
+        }
+
+        Ok(())
+    }
+
+    /// Visit a `Figure` and the image inside it.
+    fn visit_figure(&mut self, figure: &mut Figure) -> Result<(), Error> {
+        self.visit_image(&mut figure.image)
+    }
+
+    /// Visit a single `Image`.
+    fn visit_image(&mut self, image: &mut Image) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Visit a `Listing` of source code.
+    fn visit_listing(&mut self, listing: &mut Listing) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Visit a `MintedListing` of source code.
+    fn visit_minted_listing(&mut self, listing: &mut MintedListing) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Visit a `multicols` environment, then each element in its body.
+    fn visit_multicols(
+        &mut self,
+        columns: &mut usize,
+        body: &mut Vec<Element>,
+    ) -> Result<(), Error> {
+        for element in body {
+            self.visit_element(element)?;
+        }
+
+        Ok(())
+    }
+
+    /// Visit a `tikzpicture` environment and its raw TeX.
+    fn visit_tikz_picture(
+        &mut self,
+        options: &mut Option<String>,
+        body: &mut String,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Visit a `\printbibliography` command.
+    fn visit_print_bibliography(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Visit an arbitrary environment, its name, and its lines.
+    fn visit_custom_environment(
+        &mut self,
+        name: &mut String,
+        lines: &mut Vec<String>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct UppercaseSections;
+
+    impl VisitorMut for UppercaseSections {
+        fn visit_section(&mut self, section: &mut Section) -> Result<(), Error> {
+            section.name = section.name.to_uppercase();
+
+            for elem in section.iter_mut() {
+                self.visit_element(elem)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn uppercase_all_section_names() {
+        let mut doc = Document::new(DocumentClass::Article);
+        let mut section = Section::new("Introduction");
+        section.push(Section::new("Background"));
+        doc.push(section).push(Section::new("Conclusion"));
+
+        UppercaseSections.visit_document(&mut doc).unwrap();
+
+        let mut names = Vec::new();
+        for element in doc.iter() {
+            if let Element::Section(ref section) = *element {
+                names.push(section.name.clone());
+                for child in section.iter() {
+                    if let Element::Section(ref subsection) = *child {
+                        names.push(subsection.name.clone());
+                    }
+                }
+            }
+        }
+        assert_eq!(names, vec!["INTRODUCTION", "BACKGROUND", "CONCLUSION"]);
+    }
+
+    struct UppercaseText;
+
+    impl VisitorMut for UppercaseText {
+        fn visit_paragraph_element(&mut self, element: &mut ParagraphElement) -> Result<(), Error> {
+            if let ParagraphElement::Plain(ref mut text) = *element {
+                *text = text.to_uppercase();
+            }
+
+            match element.children_mut() {
+                Some(child) => self.visit_paragraph_element(child),
+                None => Ok(()),
+            }
+        }
+    }
+
+    #[test]
+    fn recurse_into_nested_paragraph_elements() {
+        let mut para = Paragraph::new();
+        para.push("Hello ")
+            .push(ParagraphElement::bold(ParagraphElement::italic("World")));
+
+        UppercaseText.visit_paragraph(&mut para).unwrap();
+
+        assert_eq!(
+            para.elements,
+            vec![
+                ParagraphElement::from("HELLO "),
+                ParagraphElement::bold(ParagraphElement::italic("WORLD")),
+            ]
+        );
+    }
+}
@@ -0,0 +1,146 @@
+use super::Visitor;
+use document::Document;
+use failure::Error;
+use paragraph::ParagraphElement;
+
+/// Count the number of words in a document's paragraphs.
+///
+/// See [`WordCount`] for exactly which parts of the document are counted.
+///
+/// [`WordCount`]: struct.WordCount.html
+pub fn count(doc: &Document) -> usize {
+    let mut counter = WordCount::new();
+    counter
+        .visit_document(doc)
+        .expect("Counting words never fails");
+    counter.words()
+}
+
+/// A `Visitor` which counts the words in every `Paragraph` of a document.
+///
+/// Only plain text is counted (including text which has been formatted, e.g.
+/// with `ParagraphElement::Bold`), wherever the paragraph appears. That
+/// includes paragraphs nested inside sections, theorems, abstracts, and
+/// containers like `Element::Center` or `Element::MultiCols`. Section titles,
+/// list items, maths, labels, references, and raw TeX (e.g. the lines of an
+/// `Element::Environment`) are all skipped, as are lone punctuation marks.
+///
+/// # Examples
+///
+/// ```rust
+/// use latex::{Document, DocumentClass, Section, Visitor, WordCount};
+///
+/// let mut section = Section::new("Introduction");
+/// section.push("Hello World!");
+/// let mut doc = Document::new(DocumentClass::Article);
+/// doc.push(section);
+///
+/// let mut counter = WordCount::new();
+/// counter.visit_document(&doc).unwrap();
+/// assert_eq!(counter.words(), 2);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WordCount {
+    words: usize,
+}
+
+impl WordCount {
+    /// Create a new `WordCount` which hasn't seen any words yet.
+    pub fn new() -> WordCount {
+        Default::default()
+    }
+
+    /// The number of words counted so far.
+    pub fn words(&self) -> usize {
+        self.words
+    }
+}
+
+impl Visitor for WordCount {
+    fn visit_paragraph_element(&mut self, element: &ParagraphElement) -> Result<(), Error> {
+        match *element {
+            ParagraphElement::Plain(ref s) => {
+                self.words += s
+                    .split_whitespace()
+                    .filter(|word| word.chars().any(char::is_alphanumeric))
+                    .count()
+            }
+            ParagraphElement::Ordinal(_) => self.words += 1,
+            _ => {}
+        }
+
+        if let Some(child) = element.children() {
+            self.visit_paragraph_element(child)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {Align, DocumentClass, Element, List, ListKind, Paragraph, Section, Theorem};
+
+    #[test]
+    fn count_words_in_a_section() {
+        let mut objectives = List::new(ListKind::Enumerate);
+        objectives.push("Create a reasonably complex document");
+
+        let mut section = Section::new("Introduction");
+        section
+            .push("This is an example paragraph.")
+            .push(Align::from("y &= mx + c"))
+            .push("Here are our objectives:")
+            .push(objectives);
+        let mut doc = Document::new(DocumentClass::Article);
+        doc.preamble.title("Hello World");
+        doc.push(Element::TitlePage).push(section);
+
+        assert_eq!(count(&doc), 5 + 4);
+    }
+
+    #[test]
+    fn math_and_references_are_skipped() {
+        let mut para = Paragraph::new();
+        para.push("Refer to ")
+            .push(ParagraphElement::reference("eq:quadratic"))
+            .push(" where ")
+            .push(ParagraphElement::InlineMath("a x^2 + b x + c".to_string()))
+            .push(" is ")
+            .push(ParagraphElement::bold("very important"));
+        let mut doc = Document::new(DocumentClass::Article);
+        doc.push(para);
+
+        assert_eq!(count(&doc), 6);
+    }
+
+    #[test]
+    fn punctuation_split_off_by_formatting_isnt_a_word() {
+        let mut para = Paragraph::new();
+        para.push("foo ")
+            .push(ParagraphElement::bold("bar"))
+            .push(". ")
+            .push(ParagraphElement::italic("baz"))
+            .push(" !");
+        let mut doc = Document::new(DocumentClass::Article);
+        doc.push(para);
+
+        assert_eq!(count(&doc), 3);
+    }
+
+    #[test]
+    fn paragraphs_inside_environments_are_counted() {
+        let mut theorem = Theorem::new("theorem");
+        theorem.push("Every integer is interesting.");
+        let mut doc = Document::new(DocumentClass::Article);
+        doc.push(Element::Center(vec!["Centred text".into()]))
+            .push(theorem)
+            .push(Element::Environment(
+                "comment".to_string(),
+                vec!["Not counted".to_string()],
+            ));
+
+        assert_eq!(count(&doc), 2 + 4);
+    }
+}